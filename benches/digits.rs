@@ -0,0 +1,127 @@
+// Times the digits module against the string-based functions day4 used to have
+//
+// Run with `cargo bench --bench digits`
+
+extern crate advent_of_code;
+
+use advent_of_code::digits;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The functions day4 used before the digits module, kept to compare against.
+mod old {
+    /// Split a number into a vector of it's digits
+    pub fn to_digits(number: usize) -> Vec<usize> {
+        use std::str::FromStr;
+        number
+            .to_string()
+            .split("")
+            .filter(|x| x != &"")
+            .map(|x| usize::from_str(x).unwrap())
+            .collect()
+    }
+
+    /// Collect a vector of digits into a number
+    pub fn to_number(digits: Vec<usize>) -> usize {
+        let mut number = 0;
+        let length = digits.len();
+        digits
+            .iter()
+            .enumerate()
+            .for_each(|(i, d)| number += d * 10_usize.pow((length - i - 1) as u32));
+        number
+    }
+
+    /// Checks whether a number has increasing digits.
+    fn has_increasing_digits(number: usize) -> bool {
+        let digits = to_digits(number);
+        for (i, d) in digits.iter().enumerate() {
+            if i > 0 && d < &digits[i - 1] {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Modify a number so it's digits are >= to the previous.
+    pub fn to_increasing_digits(number: usize) -> usize {
+        if has_increasing_digits(number) {
+            return number;
+        }
+        let digits = to_digits(number);
+        let mut satisfied: Vec<usize> = vec![];
+        digits.iter().enumerate().for_each(|(i, d)| {
+            let increase = if i > 0 && d < &satisfied[i - 1] {
+                satisfied[i - 1]
+            } else {
+                *d
+            };
+            satisfied.push(increase);
+        });
+        to_number(satisfied)
+    }
+}
+
+/// How many numbers each benchmark goes through
+const NUMBERS: u64 = 1_000_000;
+
+/// Runs `f` on each number a few times and reports the fastest run, since
+/// slower runs are mostly noise from everything else on the machine.
+fn bench<F: Fn(u64) -> u64>(name: &str, f: F) -> Duration {
+    let mut fastest = Duration::MAX;
+    for _ in 0..5 {
+        let start = Instant::now();
+        let mut total = 0u64;
+        for number in 100_000..100_000 + NUMBERS {
+            total = total.wrapping_add(f(black_box(number)));
+        }
+        black_box(total);
+        fastest = std::cmp::min(fastest, start.elapsed());
+    }
+    println!(
+        "{:<32} {:>10.2?} {:>8.1} ns/number",
+        name,
+        fastest,
+        fastest.as_nanos() as f64 / NUMBERS as f64
+    );
+    fastest
+}
+
+/// Prints how much faster the new function was.
+fn compare(name: &str, old: Duration, new: Duration) {
+    println!(
+        "{:<32} {:>10.1}x faster\n",
+        name,
+        old.as_secs_f64() / new.as_secs_f64()
+    );
+}
+
+fn main() {
+    // Both add the digits up, so both have to work out every one of them
+    let old_split = bench("old::to_digits", |n| {
+        old::to_digits(n as usize).iter().sum::<usize>() as u64
+    });
+    let new_split = bench("digits::digits", |n| digits::digits(n, 10).sum());
+    compare("splitting", old_split, new_split);
+
+    let old_round = bench("old::to_number(to_digits)", |n| {
+        old::to_number(old::to_digits(n as usize)) as u64
+    });
+    let new_round = bench("digits::from_digits(digits)", |n| {
+        digits::from_digits(digits::digits(n, 10), 10).unwrap()
+    });
+    compare("round trip", old_round, new_round);
+
+    let old_next = bench("old::to_increasing_digits", |n| {
+        old::to_increasing_digits(n as usize) as u64
+    });
+    let new_next = bench("digits::next_non_decreasing", |n| {
+        digits::next_non_decreasing(n, 10).unwrap()
+    });
+    compare("non-decreasing", old_next, new_next);
+
+    bench("digits::digit_sum", |n| digits::digit_sum(n, 10));
+    bench("digits::is_palindrome", |n| {
+        digits::is_palindrome(n, 10) as u64
+    });
+}
@@ -0,0 +1,44 @@
+// Generates a test for every puzzle example in fixtures/, see src/fixture.rs
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[path = "src/fixture/walk.rs"]
+mod walk;
+
+/// A test name from a fixture's path, like `y2019_day03_example1`
+fn test_name(relative: &Path) -> String {
+    let name: String = relative
+        .with_extension("")
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("y{}", name.trim_start_matches('_'))
+}
+
+fn main() {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("fixtures");
+    println!("cargo:rerun-if-changed=fixtures");
+    println!("cargo:rerun-if-changed=src/fixture/walk.rs");
+    let paths = walk::paths(&root);
+
+    let mut generated = String::new();
+    for path in &paths {
+        // Cargo only notices new files in directories it's watching
+        if let Some(parent) = path.parent() {
+            println!("cargo:rerun-if-changed={}", parent.display());
+        }
+        let relative = path.strip_prefix(&root).unwrap();
+        generated.push_str(&format!(
+            "#[test]\nfn {}() {{\n    check({:?});\n}}\n\n",
+            test_name(relative),
+            path.display().to_string()
+        ));
+    }
+    generated.push_str(&format!("const FIXTURES: usize = {};\n", paths.len()));
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("fixtures.rs");
+    fs::write(out, generated).unwrap();
+}
@@ -0,0 +1,297 @@
+// Timing solutions over many runs, and comparing against earlier timings
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::*;
+    use crate::solution;
+    use std::time::Duration;
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+    #[test]
+    fn test_stats() {
+        let mut samples: Vec<Duration> = (1..=100).rev().map(ms).collect();
+        let stats = Stats::of(&mut samples);
+        assert_eq!(
+            (stats.min, stats.median, stats.p95),
+            (ms(1), ms(50), ms(95))
+        );
+        assert_eq!(stats.runs, 100);
+        let stats = Stats::of(&mut [ms(3)]);
+        assert_eq!((stats.min, stats.median, stats.p95), (ms(3), ms(3), ms(3)));
+        assert_eq!(Stats::of(&mut []).runs, 0);
+    }
+    #[test]
+    fn test_bench_day() {
+        let day = solution::find(2019, 4).unwrap();
+        let options = Options { warmup: 1, runs: 3 };
+        let measurements = bench_day(&day, "165432-707912", &options).unwrap();
+        let stages: Vec<Stage> = measurements.iter().map(|m| m.stage).collect();
+        assert_eq!(stages, vec![Stage::Parse, Stage::Part1, Stage::Part2]);
+        assert!(measurements
+            .iter()
+            .all(|m| m.stats.runs == 3 && m.bytes == 13));
+        assert!(bench_day(&day, "oops", &options).is_err());
+    }
+    #[test]
+    fn test_baseline() {
+        let measurement = |stage, median| Measurement {
+            year: 2019,
+            day: 4,
+            stage,
+            bytes: 13,
+            stats: Stats {
+                min: median,
+                median,
+                p95: median,
+                runs: 1,
+            },
+        };
+        let mut baseline = Baseline::default();
+        baseline.record(&measurement(Stage::Part1, ms(10)));
+        let saved = baseline.to_string();
+        assert_eq!(
+            saved,
+            "# year\tday\tstage\tmedian_ns\n2019\t4\tpart1\t10000000\n"
+        );
+        let baseline: Baseline = saved.parse().unwrap();
+        // Within the threshold is fine, past it is a regression
+        assert_eq!(
+            baseline.regression(&measurement(Stage::Part1, ms(11)), 0.2),
+            None
+        );
+        assert_eq!(
+            baseline.regression(&measurement(Stage::Part1, ms(15)), 0.2),
+            Some(1.5)
+        );
+        // Nothing to compare against
+        assert_eq!(
+            baseline.regression(&measurement(Stage::Part2, ms(99)), 0.2),
+            None
+        );
+        assert!("2019\t4\tpart3\t10".parse::<Baseline>().is_err());
+        assert!("2019\t4\tpart1".parse::<Baseline>().is_err());
+    }
+}
+
+use crate::solution::{Day, Part};
+use std::collections::HashMap;
+use std::fmt;
+use std::hint::black_box;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// What part of a day is being timed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Parse, Stage::Part1, Stage::Part2];
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        }
+    }
+}
+
+impl From<Part> for Stage {
+    fn from(part: Part) -> Stage {
+        match part {
+            Part::One => Stage::Part1,
+            Part::Two => Stage::Part2,
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Stage {
+    type Err = String;
+    fn from_str(string: &str) -> Result<Stage, String> {
+        Stage::ALL
+            .iter()
+            .copied()
+            .find(|stage| stage.name() == string)
+            .ok_or_else(|| format!("unknown stage {:?}", string))
+    }
+}
+
+/// How many times to run each stage.
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    /// Untimed runs first, to warm up caches
+    pub warmup: usize,
+    /// Timed runs
+    pub runs: usize,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            warmup: 3,
+            runs: 20,
+        }
+    }
+}
+
+/// A summary of how long some runs took.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    /// 95% of runs were at least this fast
+    pub p95: Duration,
+    pub runs: usize,
+}
+
+impl Stats {
+    /// Summarizes `samples`, sorting them on the way
+    pub fn of(samples: &mut [Duration]) -> Stats {
+        samples.sort_unstable();
+        // The nearest rank: the smallest sample at least `percent`% of the
+        // samples are no bigger than
+        let percentile = |percent: usize| match samples.len() {
+            0 => Duration::ZERO,
+            len => samples[(len * percent).div_ceil(100).max(1) - 1],
+        };
+        Stats {
+            min: samples.first().copied().unwrap_or_default(),
+            median: percentile(50),
+            p95: percentile(95),
+            runs: samples.len(),
+        }
+    }
+}
+
+/// How long one stage of a day took.
+#[derive(Clone, Copy, Debug)]
+pub struct Measurement {
+    pub year: u32,
+    pub day: u32,
+    pub stage: Stage,
+    /// The size of the puzzle input
+    pub bytes: usize,
+    pub stats: Stats,
+}
+
+impl Measurement {
+    /// Input bytes handled per second, by the median run
+    pub fn throughput(&self) -> f64 {
+        self.bytes as f64 / self.stats.median.as_secs_f64()
+    }
+}
+
+/// Times `f` `options.runs` times, after warming up.
+fn time<T, F: FnMut() -> T>(options: &Options, mut f: F) -> Stats {
+    for _ in 0..options.warmup {
+        black_box(f());
+    }
+    let mut samples: Vec<Duration> = (0..options.runs)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::of(&mut samples)
+}
+
+/// Times parsing `input` for `day`, then each part on its own, so slow
+/// parsing doesn't hide in the parts' timings.
+pub fn bench_day(day: &Day, input: &str, options: &Options) -> Result<Vec<Measurement>, String> {
+    let parsed = day.parse(input)?;
+    for &part in &Part::ALL {
+        day.solve(&parsed, part)
+            .map_err(|error| format!("part {}: {}", part, error))?;
+    }
+    let measurement = |stage, stats| Measurement {
+        year: day.year,
+        day: day.day,
+        stage,
+        bytes: input.len(),
+        stats,
+    };
+    let mut measurements = vec![measurement(
+        Stage::Parse,
+        time(options, || day.parse(input)),
+    )];
+    for &part in &Part::ALL {
+        let stats = time(options, || day.solve(&parsed, part));
+        measurements.push(measurement(part.into(), stats));
+    }
+    Ok(measurements)
+}
+
+/// Median timings from an earlier run, to spot regressions. Saved as tab
+/// separated year, day, stage, and median in nanoseconds.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Baseline {
+    medians: HashMap<(u32, u32, Stage), u128>,
+}
+
+impl Baseline {
+    /// Remember `measurement`'s median, replacing any older one
+    pub fn record(&mut self, measurement: &Measurement) {
+        let key = (measurement.year, measurement.day, measurement.stage);
+        self.medians
+            .insert(key, measurement.stats.median.as_nanos());
+    }
+    /// How many times slower `measurement` is than the baseline, if that's
+    /// more than `threshold` (e.g. 0.1 for 10%) slower.
+    pub fn regression(&self, measurement: &Measurement, threshold: f64) -> Option<f64> {
+        let key = (measurement.year, measurement.day, measurement.stage);
+        let baseline = *self.medians.get(&key)? as f64;
+        let ratio = measurement.stats.median.as_nanos() as f64 / baseline;
+        if ratio > 1.0 + threshold {
+            Some(ratio)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# year\tday\tstage\tmedian_ns")?;
+        let mut medians: Vec<_> = self.medians.iter().collect();
+        medians.sort_by_key(|&(&(year, day, stage), _)| (year, day, stage.name()));
+        for ((year, day, stage), median) in medians {
+            writeln!(f, "{}\t{}\t{}\t{}", year, day, stage, median)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = String;
+    fn from_str(string: &str) -> Result<Baseline, String> {
+        let mut baseline = Baseline::default();
+        for (i, line) in string.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            let bad = |what: &str| format!("line {}: {}", i + 1, what);
+            if fields.len() != 4 {
+                return Err(bad("expected year, day, stage, and median"));
+            }
+            let year = fields[0].parse().map_err(|_| bad("bad year"))?;
+            let day = fields[1].parse().map_err(|_| bad("bad day"))?;
+            let stage = fields[2].parse().map_err(|error: String| bad(&error))?;
+            let median = fields[3].parse().map_err(|_| bad("bad median"))?;
+            baseline.medians.insert((year, day, stage), median);
+        }
+        Ok(baseline)
+    }
+}
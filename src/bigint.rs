@@ -0,0 +1,199 @@
+// A small arbitrary-precision unsigned integer, for sums too big for a u128
+
+#[cfg(test)]
+mod tests {
+    use crate::bigint::*;
+    use std::str::FromStr;
+    #[test]
+    fn test_from_and_display() {
+        assert_eq!(BigUint::new().to_string(), "0");
+        assert_eq!(BigUint::from(7).to_string(), "7");
+        assert_eq!(BigUint::from(1_000_000_000).to_string(), "1000000000");
+        assert_eq!(BigUint::from(u128::MAX).to_string(), u128::MAX.to_string());
+    }
+    #[test]
+    fn test_from_str() {
+        let digits = "123456789012345678901234567890123456789012345";
+        assert_eq!(BigUint::from_str(digits).unwrap().to_string(), digits);
+        assert_eq!(BigUint::from_str("000120").unwrap(), BigUint::from(120));
+        assert!(BigUint::from_str("").is_err());
+        assert!(BigUint::from_str("12a").is_err());
+    }
+    #[test]
+    fn test_add() {
+        let mut sum = BigUint::from(u128::MAX);
+        sum += 1;
+        assert_eq!(sum.to_string(), "340282366920938463463374607431768211456");
+        assert_eq!(sum.to_u128(), None);
+        let mut sum = BigUint::new();
+        for _ in 0..1000 {
+            sum += 99_999_999_999_999_999_999;
+        }
+        assert_eq!(sum.to_string(), "99999999999999999999000");
+        let mut double = sum.clone();
+        double += &sum;
+        assert_eq!(double.to_string(), "199999999999999999998000");
+        assert_eq!(double.to_u128(), Some(199_999_999_999_999_999_998_000));
+    }
+    #[test]
+    fn test_ordering() {
+        assert!(BigUint::from(10) > BigUint::from(9));
+        assert!(
+            BigUint::from(u128::MAX)
+                < BigUint::from_str("1000000000000000000000000000000000000000").unwrap()
+        );
+    }
+}
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::AddAssign;
+use std::str::FromStr;
+
+/// Each limb holds nine decimal digits, which makes printing and parsing easy.
+const BASE: u32 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+/// An unsigned integer of any size.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    /// Base 10^9 limbs, least significant first, without trailing zero limbs
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    /// Zero
+    pub fn new() -> BigUint {
+        BigUint { limbs: vec![] }
+    }
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+    /// The number as a u128, if it fits
+    pub fn to_u128(&self) -> Option<u128> {
+        self.limbs.iter().rev().try_fold(0u128, |number, &limb| {
+            number.checked_mul(BASE as u128)?.checked_add(limb as u128)
+        })
+    }
+    /// Add some base 10^9 limbs to this number, carrying as needed
+    fn add_limbs<I: IntoIterator<Item = u32>>(&mut self, other: I) {
+        let mut carry = 0;
+        let mut i = 0;
+        for limb in other {
+            if i == self.limbs.len() {
+                self.limbs.push(0);
+            }
+            let sum = self.limbs[i] + limb + carry;
+            self.limbs[i] = sum % BASE;
+            carry = sum / BASE;
+            i += 1;
+        }
+        while carry > 0 {
+            if i == self.limbs.len() {
+                self.limbs.push(0);
+            }
+            let sum = self.limbs[i] + carry;
+            self.limbs[i] = sum % BASE;
+            carry = sum / BASE;
+            i += 1;
+        }
+    }
+}
+
+/// The base 10^9 limbs of a u128, least significant first
+fn limbs_of(mut number: u128) -> Vec<u32> {
+    let mut limbs = vec![];
+    while number > 0 {
+        limbs.push((number % BASE as u128) as u32);
+        number /= BASE as u128;
+    }
+    limbs
+}
+
+impl From<u128> for BigUint {
+    fn from(number: u128) -> BigUint {
+        BigUint {
+            limbs: limbs_of(number),
+        }
+    }
+}
+
+impl AddAssign<u128> for BigUint {
+    fn add_assign(&mut self, other: u128) {
+        self.add_limbs(limbs_of(other));
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        self.add_limbs(other.limbs.iter().cloned());
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Without trailing zero limbs, more limbs means a bigger number
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.limbs.split_last() {
+            None => write!(f, "0"),
+            Some((most, rest)) => {
+                write!(f, "{}", most)?;
+                for limb in rest.iter().rev() {
+                    write!(f, "{:09}", limb)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A string that isn't a non-empty run of decimal digits.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseBigUintError;
+
+impl fmt::Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid digit found in string")
+    }
+}
+
+impl std::error::Error for ParseBigUintError {}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+    fn from_str(string: &str) -> Result<BigUint, ParseBigUintError> {
+        if string.is_empty() || !string.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseBigUintError);
+        }
+        let bytes = string.as_bytes();
+        let mut limbs = vec![];
+        // Take nine digits at a time from the least significant end
+        let mut end = bytes.len();
+        while end > 0 {
+            let start = end.saturating_sub(BASE_DIGITS);
+            let limb = bytes[start..end]
+                .iter()
+                .fold(0, |limb, b| limb * 10 + (b - b'0') as u32);
+            limbs.push(limb);
+            end = start;
+        }
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Ok(BigUint { limbs })
+    }
+}
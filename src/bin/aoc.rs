@@ -0,0 +1,352 @@
+extern crate advent_of_code;
+
+use advent_of_code::bench::{self, Baseline, Options};
+use advent_of_code::isolate::{self, Limits};
+use advent_of_code::log::{self, Level};
+use advent_of_code::output::{self, Format, Output};
+use advent_of_code::scaffold::NewDay;
+use advent_of_code::solution::{self, Answer, Day, Part};
+use advent_of_code::watch::{self, Watcher};
+use std::env;
+use std::fs;
+use std::process::{self, Command};
+use std::thread;
+use std::time::Duration;
+
+const USAGE: &str = "\
+Usage: aoc <command> [options]
+
+Commands:
+  run      Solve each day and print the answers
+  bench    Time each day's parsing and parts
+  new-day  Start a new day from a template (needs --year and --day)
+  watch    Rerun a day's tests and solution when its files change
+
+Options:
+  --year <year>          Only the days from this year
+  --day <day>            Only this day
+  --format <format>      text, json, or tsv (run, default text)
+  --timeout <seconds>    Stop a part that runs longer than this (run, default 60)
+  --max-rss <MB>         Stop a part that uses more memory than this (run, Linux only)
+  --runs <n>             Timed runs per stage (bench, default 20)
+  --warmup <n>           Untimed runs first (bench, default 3)
+  --baseline <file>      Timings to compare against (bench, default bench/baseline.tsv)
+  --save-baseline        Save these timings as the new baseline (bench)
+  --threshold <percent>  How much slower counts as a regression (bench, default 10)
+  --interval <ms>        How often to check for changes (watch, default 500)
+  -v, --verbose          Say what's happening on stderr, twice for more detail
+  --quiet                Only print errors on stderr";
+
+/// The command line, split into flags and their values.
+struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    fn has(&self, flag: &str) -> bool {
+        self.args.iter().any(|arg| arg == flag)
+    }
+    /// The value after `flag`, parsed, if `flag` is there
+    fn optional<T: std::str::FromStr>(&self, flag: &str) -> Option<T> {
+        let i = self.args.iter().position(|arg| arg == flag)?;
+        match self.args.get(i + 1).map(|value| value.parse()) {
+            Some(Ok(value)) => Some(value),
+            _ => fail(&format!("{} needs a valid value", flag)),
+        }
+    }
+    /// The value after `flag`, parsed, or `default` if it isn't there
+    fn value<T: std::str::FromStr>(&self, flag: &str, default: T) -> T {
+        self.optional(flag).unwrap_or(default)
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}\n\n{}", message, USAGE);
+    process::exit(2);
+}
+
+/// The registered days picked by `--year` and `--day`.
+fn selected_days(args: &Args) -> Vec<Day> {
+    let year: Option<u32> = args.optional("--year");
+    let day: Option<u32> = args.optional("--day");
+    let days = match year {
+        Some(year) => solution::year(year),
+        None => solution::registry(),
+    };
+    let days: Vec<Day> = days
+        .into_iter()
+        .filter(|found| day.map_or(true, |day| found.day == day))
+        .collect();
+    if days.is_empty() {
+        fail("No solutions match");
+    }
+    days
+}
+
+/// Solves `part` of `day` in a child process, stopping it if it goes past
+/// `limits`, so one bad part can't stop the rest. The child logs as much as
+/// this process does.
+fn isolated(day: &Day, part: Part, limits: &Limits, args: &Args) -> output::Record {
+    let exe = env::current_exe().unwrap_or_else(|error| fail(&error.to_string()));
+    let mut command = Command::new(exe);
+    command.args([
+        "part".to_string(),
+        "--year".to_string(),
+        day.year.to_string(),
+        "--day".to_string(),
+        day.day.to_string(),
+        "--part".to_string(),
+        part.to_string(),
+    ]);
+    command.args(
+        args.args
+            .iter()
+            .filter(|arg| ["-v", "--verbose", "--quiet"].contains(&arg.as_str())),
+    );
+    advent_of_code::log!(Info, "solving {} day {} part {}", day.year, day.day, part);
+    match isolate::run(&mut command, limits) {
+        Ok(finished) => {
+            advent_of_code::log!(
+                Debug,
+                "child finished in {:.2?}: {:?}",
+                finished.elapsed,
+                finished.outcome
+            );
+            finished.record(day, part)
+        }
+        Err(error) => output::failed(day, &format!("couldn't start a child process: {}", error))
+            .into_iter()
+            .find(|record| record.part == part)
+            .expect("a record for each part"),
+    }
+}
+
+fn run(args: &Args) {
+    let format = Format::from_args(&args.args).unwrap_or_else(|error| fail(&error));
+    let timeout = Duration::try_from_secs_f64(args.value("--timeout", 60.0))
+        .unwrap_or_else(|error| fail(&format!("--timeout needs a valid value: {}", error)));
+    let max_rss = args.optional::<u64>("--max-rss").map(|mb| {
+        mb.checked_mul(1 << 20)
+            .unwrap_or_else(|| fail("--max-rss is more memory than there can be"))
+    });
+    let limits = Limits { timeout, max_rss };
+    let mut output = Output::stdout(format);
+    for day in selected_days(args) {
+        let records: Vec<_> = match day.read_input() {
+            Ok(_) => Part::ALL
+                .iter()
+                .map(|&part| isolated(&day, part, &limits, args))
+                .collect(),
+            Err(error) => output::failed(&day, &format!("couldn't read {}: {}", day.input, error)),
+        };
+        output.records(&records).unwrap();
+    }
+}
+
+/// Solves one part and reports it for `run`, in the child process.
+fn part(args: &Args) {
+    let day = match selected_days(args).as_slice() {
+        [day] => *day,
+        _ => fail("part needs a single day, picked by --year and --day"),
+    };
+    let part = match args.optional::<u32>("--part") {
+        Some(1) => Part::One,
+        Some(2) => Part::Two,
+        _ => fail("part needs --part 1 or --part 2"),
+    };
+    let input = day
+        .read_input()
+        .unwrap_or_else(|error| fail(&format!("couldn't read {}: {}", day.input, error)));
+    print!(
+        "{}",
+        isolate::report(&output::solve_part(&day, &input, part))
+    );
+}
+
+fn bench(args: &Args) {
+    let options = Options {
+        warmup: args.value("--warmup", Options::default().warmup),
+        runs: args.value("--runs", Options::default().runs),
+    };
+    let baseline_path: String = args.value("--baseline", "bench/baseline.tsv".to_string());
+    let threshold = args.value("--threshold", 10.0) / 100.0;
+    let baseline: Baseline = match fs::read_to_string(&baseline_path) {
+        Ok(saved) => saved
+            .parse()
+            .unwrap_or_else(|error| fail(&format!("Bad baseline {}: {}", baseline_path, error))),
+        Err(_) => Baseline::default(),
+    };
+
+    let mut current = baseline.clone();
+    let mut regressions = 0;
+    println!(
+        "{:<12} {:<6} {:>12} {:>12} {:>12} {:>12}",
+        "day", "stage", "min", "median", "p95", "MB/s"
+    );
+    for day in selected_days(args) {
+        let name = format!("{} day {}", day.year, day.day);
+        advent_of_code::log!(Info, "benchmarking {}", name);
+        let measurements = day
+            .read_input()
+            .map_err(|error| error.to_string())
+            .and_then(|input| bench::bench_day(&day, &input, &options));
+        let measurements = match measurements {
+            Ok(measurements) => measurements,
+            Err(error) => {
+                println!("{:<12} skipped: {}", name, error);
+                continue;
+            }
+        };
+        for measurement in measurements {
+            let stats = measurement.stats;
+            print!(
+                "{:<12} {:<6} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2}",
+                name,
+                measurement.stage,
+                stats.min,
+                stats.median,
+                stats.p95,
+                measurement.throughput() / 1e6
+            );
+            match baseline.regression(&measurement, threshold) {
+                Some(ratio) => {
+                    regressions += 1;
+                    println!("  REGRESSION: {:.2}x the baseline", ratio);
+                }
+                None => println!(),
+            }
+            current.record(&measurement);
+        }
+    }
+
+    if args.has("--save-baseline") {
+        if let Some(parent) = std::path::Path::new(&baseline_path).parent() {
+            fs::create_dir_all(parent).ok();
+        }
+        fs::write(&baseline_path, current.to_string())
+            .unwrap_or_else(|error| fail(&format!("Couldn't save {}: {}", baseline_path, error)));
+        println!("Saved the baseline to {}", baseline_path);
+    }
+    if regressions > 0 {
+        eprintln!("{} regression(s) past {}%", regressions, threshold * 100.0);
+        process::exit(1);
+    }
+}
+
+fn new_day(args: &Args) {
+    let (year, day) = match (args.optional("--year"), args.optional("--day")) {
+        (Some(year), Some(day)) => (year, day),
+        _ => fail("new-day needs --year and --day"),
+    };
+    let new = NewDay::new(year, day).unwrap_or_else(|error| fail(&error));
+    let generated = match solution::find(year, day) {
+        Some(found) => Err(format!("it's already registered, see {:?}", found)),
+        None => new.generate("."),
+    };
+    match generated {
+        Ok(created) => {
+            for path in created {
+                println!("Created {}", path.display());
+            }
+            println!("Registered {} day {}", year, day);
+        }
+        Err(error) => {
+            eprintln!("Couldn't start {} day {}: {}", year, day, error);
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs cargo with `args`, returning whether it worked and what it printed
+/// to standard output and standard error.
+fn cargo(args: &[&str]) -> (bool, String, String) {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    match Command::new(cargo).args(args).output() {
+        Ok(output) => (
+            output.status.success(),
+            String::from_utf8_lossy(&output.stdout).into_owned(),
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        ),
+        Err(error) => (
+            false,
+            String::new(),
+            format!("couldn't run cargo: {}", error),
+        ),
+    }
+}
+
+/// Runs `day`'s tests and solution, printing how its answers changed since
+/// `previous`, and returns them.
+fn check(day: &Day, previous: &[(String, Answer)]) -> Vec<(String, Answer)> {
+    let year = day.year.to_string();
+    let number = day.day.to_string();
+    let module = format!("y{}::day{:02}::", day.year, day.day);
+    let fixtures = format!("y{}_day{:02}_", day.year, day.day);
+    let tests = cargo(&[
+        "test", "--quiet", "--lib", "--test", "fixtures", "--", &module, &fixtures,
+    ]);
+    let run = cargo(&[
+        "run", "--quiet", "--bin", "aoc", "--", "run", "--year", &year, "--day", &number,
+        "--format", "tsv",
+    ]);
+
+    println!("== {} day {} ==", day.year, day.day);
+    let answers = watch::answers(&run.1);
+    if run.0 {
+        for line in watch::diff(previous, &answers) {
+            println!("{}", line);
+        }
+    } else {
+        print!("{}", run.2);
+        println!("solution: FAILED");
+    }
+    match tests {
+        (true, _, _) => println!("tests: ok"),
+        (false, out, err) => {
+            print!("{}{}", out, err);
+            println!("tests: FAILED");
+        }
+    }
+    answers
+}
+
+fn watch(args: &Args) {
+    let days = selected_days(args);
+    let day = match days.as_slice() {
+        [day] => *day,
+        _ => fail("watch needs a single day, picked by --year and --day"),
+    };
+    let interval = Duration::from_millis(args.value("--interval", 500));
+    let watched = watch::watched(&day);
+    advent_of_code::log!(Info, "watching {:?}", watched);
+    let mut watcher = Watcher::new(watched);
+    let mut answers = check(&day, &[]);
+    loop {
+        thread::sleep(interval);
+        let changed = watcher.changed();
+        if changed.is_empty() {
+            continue;
+        }
+        println!();
+        for path in changed {
+            println!("Changed {}", path.display());
+        }
+        answers = check(&day, &answers);
+    }
+}
+
+fn main() {
+    let args = Args {
+        args: std::env::args().skip(1).collect(),
+    };
+    log::set_level(Level::from_args(&args.args));
+    match args.args.first().map(String::as_str) {
+        Some("run") => run(&args),
+        Some("bench") => bench(&args),
+        Some("new-day") => new_day(&args),
+        Some("watch") => watch(&args),
+        Some("part") => part(&args),
+        Some("--help") | Some("-h") => println!("{}", USAGE),
+        _ => fail("Expected a command"),
+    }
+}
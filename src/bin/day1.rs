@@ -1,25 +1,61 @@
 extern crate advent_of_code;
 
-use advent_of_code::day1;
 use advent_of_code::input;
-use std::str::FromStr;
+use advent_of_code::log;
+use advent_of_code::output::{Format, Output, Record};
+use advent_of_code::solution::Part;
+use advent_of_code::y2019::day01::{FuelModel, FuelTotals};
+use std::time::{Duration, Instant};
 
 fn main() {
-    let input = input::read_file_to_string("input/day1part1");
+    let args: Vec<String> = std::env::args().collect();
+    log::set_level(log::Level::from_args(&args));
+    let format = Format::from_args(&args).unwrap_or_else(|error| panic!("{}", error));
+    // `--stdin` reads the masses from standard input instead of the input file
+    let stdin = args.iter().any(|arg| arg == "--stdin");
+    let source = input::open("input/day1part1", stdin);
 
-    // Part 1
-    let mut fuel = 0;
-    for line in input.lines() {
-        let mass: usize = usize::from_str(line).unwrap();
-        fuel += day1::fuel_from_mass(mass);
+    // Sum exactly, so stress inputs with huge masses can't overflow. The
+    // masses are parsed as they stream in, so parsing counts as solving.
+    let start = Instant::now();
+    let mut totals = FuelTotals::new();
+    let mut failure = None;
+    for record in input::records::<u128, _>(source) {
+        let added = record
+            .map_err(|error| error.to_string())
+            .and_then(|record| {
+                totals
+                    .add(&FuelModel::STANDARD, record.value)
+                    .map_err(|overflow| overflow.to_string())
+            });
+        if let Err(error) = added {
+            failure = Some(error);
+            break;
+        }
     }
-    println!("Fuel: {}", fuel);
+    let solve = start.elapsed();
 
-    // Part 2
-    let mut fuel_fuel = 0;
-    for line in input.lines() {
-        let mass: usize = usize::from_str(line).unwrap();
-        fuel_fuel += day1::fuel_for_module(mass);
-    }
-    println!("Fuel fuel: {}", fuel_fuel);
+    let answer = |total: &dyn ToString| match &failure {
+        Some(error) => Err(error.clone()),
+        None => Ok(total.to_string()),
+    };
+    let record = |part, answer| Record {
+        year: 2019,
+        day: 1,
+        part,
+        variant: None,
+        answer,
+        stopped: None,
+        parse: Duration::ZERO,
+        solve,
+    };
+    let mut output = Output::stdout(format);
+    output
+        .records(&[
+            // Part 1
+            record(Part::One, answer(&totals.fuel)),
+            // Part 2
+            record(Part::Two, answer(&totals.total)),
+        ])
+        .unwrap();
 }
@@ -1,32 +1,20 @@
 extern crate advent_of_code;
 
-use advent_of_code::day2;
 use advent_of_code::input;
+use advent_of_code::log;
+use advent_of_code::output::{self, Format, Output};
+use advent_of_code::solution;
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    log::set_level(log::Level::from_args(&args));
+    let format = Format::from_args(&args).unwrap_or_else(|error| panic!("{}", error));
     let input = input::read_file_to_string("input/day2part2");
 
-    // # Part 1
-    let mut program = day2::string_to_intcode(&input);
-    program[1] = 12;
-    program[2] = 2;
-    day2::intcode_execute(&mut program);
-    println!("{}", program[0]);
-
-    // # Part 2
-    for noun in 0..=99 {
-        for verb in 0..=99 {
-            program = day2::string_to_intcode(&input);
-            program[1] = noun;
-            program[2] = verb;
-            day2::intcode_execute(&mut program);
-            if program[0] == 19690720 {
-                println!("{}", 100 * noun + verb);
-                break;
-            }
-        }
-        if program[0] == 19690720 {
-            break;
-        }
-    }
+    // Part 1 restores the program to its 1202 alarm state, part 2 searches
+    // for the noun and verb that output 19690720
+    let day = solution::find(2019, 2).unwrap();
+    Output::stdout(format)
+        .records(&output::solve(&day, &input))
+        .unwrap();
 }
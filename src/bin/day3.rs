@@ -1,27 +1,41 @@
 extern crate advent_of_code;
 
-use advent_of_code::day3;
 use advent_of_code::input;
+use advent_of_code::log;
+use advent_of_code::output::{self, Format, Output};
+use advent_of_code::render;
+use advent_of_code::solution;
+use advent_of_code::y2019::day03;
+use std::io::Write;
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    log::set_level(log::Level::from_args(&args));
+    let format = Format::from_args(&args).unwrap_or_else(|error| panic!("{}", error));
     let input = input::read_file_to_string("input/day3");
 
-    let mut wires = input.lines();
-    let red_path = day3::string_to_path(wires.next().unwrap());
-    let green_path = day3::string_to_path(wires.next().unwrap());
-    let red = day3::trace_wire(red_path);
-    let green = day3::trace_wire(green_path);
-    let intersections = day3::wire_intersections(&red, &green);
+    // Part 1 is the closest crossing to the port, part 2 the one the wires
+    // reach soonest
+    let day = solution::find(2019, 3).unwrap();
+    let mut output = Output::stdout(format);
+    output.records(&output::solve(&day, &input)).unwrap();
 
-    // # Part 1
-    let closest = intersections.iter().map(|x| x.distance()).min().unwrap();
-    println!("{}", closest);
-
-    // # Part 2
-    let shortest = intersections
-        .iter()
-        .map(|x| day3::wire_length_to(&red, x) + day3::wire_length_to(&green, x))
-        .min()
-        .unwrap();
-    println!("{}", shortest);
+    // # Rendering
+    // `--render` draws the wires in the terminal and saves them to day3.svg.
+    // The drawing goes to stderr unless the answers are plain text, so it
+    // doesn't get mixed into them.
+    if args.iter().any(|arg| arg == "--render") {
+        let wires: Vec<_> = day03::parse_wires(&input)
+            .unwrap_or_else(|error| panic!("Invalid wires: {}", error))
+            .into_iter()
+            .map(day03::trace_wire)
+            .collect();
+        let mut drawing: Box<dyn Write> = match output.format() {
+            Format::Text => Box::new(std::io::stdout()),
+            _ => Box::new(std::io::stderr()),
+        };
+        write!(drawing, "{}", render::ascii(&wires, 120, 60)).unwrap();
+        std::fs::write("day3.svg", render::svg(&wires)).unwrap();
+        writeln!(drawing, "Saved day3.svg").unwrap();
+    }
 }
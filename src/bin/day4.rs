@@ -0,0 +1,75 @@
+extern crate advent_of_code;
+
+use advent_of_code::input;
+use advent_of_code::log;
+use advent_of_code::output::{Format, Output, Record};
+use advent_of_code::solution::Part;
+use advent_of_code::y2019::day04::{
+    self, AtLeastOnePair, Ends, ExactlyTwoRun, NonDecreasing, PasswordRule,
+};
+use std::time::{Duration, Instant};
+
+/// Runs `f`, returning its answer and how long it took.
+fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let answer = f();
+    (answer, start.elapsed())
+}
+
+/// Counts the passwords following `rule` both ways, and makes sure they agree.
+fn solve<R: PasswordRule + Sync>(
+    part: Part,
+    rule: &R,
+    bounds: (std::ops::Bound<u64>, std::ops::Bound<u64>),
+    parse: Duration,
+) -> [Record; 2] {
+    let (closed_form, closed_form_time) = timed(|| rule.count(bounds));
+    let (brute, brute_time) = timed(|| day04::brute_count(bounds, rule));
+    let record = |variant, answer, solve| Record {
+        year: 2019,
+        day: 4,
+        part,
+        variant: Some(variant),
+        answer,
+        stopped: None,
+        parse,
+        solve,
+    };
+    let brute = if brute == closed_form {
+        Ok(brute.to_string())
+    } else {
+        Err(format!("{} disagrees with the closed form", brute))
+    };
+    [
+        record("closed form", Ok(closed_form.to_string()), closed_form_time),
+        record("brute force", brute, brute_time),
+    ]
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    log::set_level(log::Level::from_args(&args));
+    let format = Format::from_args(&args).unwrap_or_else(|error| panic!("{}", error));
+    let input = input::read_file_to_string("input/day4");
+    let (range, parse) = timed(|| day04::parse_range(&input));
+    let (lower, upper) = range.unwrap_or_else(|error| panic!("Invalid range: {}", error));
+
+    // The puzzle's range includes its ends. `--exclusive` leaves them out,
+    // like `within_range`.
+    let ends = if args.iter().any(|arg| arg == "--exclusive") {
+        Ends::Exclusive
+    } else {
+        Ends::Inclusive
+    };
+    let bounds = ends.bounds(lower, upper);
+
+    let mut output = Output::stdout(format);
+    let part1 = NonDecreasing.and(AtLeastOnePair);
+    output
+        .records(&solve(Part::One, &part1, bounds, parse))
+        .unwrap();
+    let part2 = NonDecreasing.and(ExactlyTwoRun);
+    output
+        .records(&solve(Part::Two, &part2, bounds, parse))
+        .unwrap();
+}
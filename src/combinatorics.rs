@@ -0,0 +1,292 @@
+// Counting things exactly, and listing permutations and combinations
+
+#[cfg(test)]
+mod tests {
+    use crate::combinatorics::*;
+    #[test]
+    fn test_factorial() {
+        assert_eq!(factorial(0), Some(1));
+        assert_eq!(factorial(1), Some(1));
+        assert_eq!(factorial(2), Some(2));
+        assert_eq!(factorial(3), Some(6));
+        assert_eq!(factorial(10), Some(3628800));
+        assert_eq!(factorial(34), Some(295232799039604140847618609643520000000));
+        assert_eq!(factorial(35), None);
+    }
+    #[test]
+    fn test_choose() {
+        assert_eq!(choose(1, 0), 1);
+        assert_eq!(choose(1, 1), 1);
+        assert_eq!(choose(2, 2), 1);
+        assert_eq!(choose(2, 1), 2);
+        assert_eq!(choose(10, 0), 1);
+        assert_eq!(choose(10, 3), 120);
+        assert_eq!(choose(3, 4), 0);
+        // usize factorials overflowed here
+        assert_eq!(choose(21, 10), 352716);
+        assert_eq!(choose(100, 50), 100891344545564193334812497256);
+        assert_eq!(
+            checked_choose(130, 65),
+            Some(95067625827960698145584333020095113100)
+        );
+        assert_eq!(checked_choose(132, 66), None);
+        assert_eq!(
+            checked_choose(u64::MAX, u64::MAX - 1),
+            Some(u64::MAX as u128)
+        );
+    }
+    #[test]
+    fn test_pascal() {
+        let pascal = Pascal::new(140);
+        assert_eq!(pascal.get(4, 2), Some(6));
+        assert_eq!(pascal.get(4, 5), Some(0));
+        assert_eq!(pascal.get(141, 0), None);
+        for n in 0..=140 {
+            for k in 0..=n {
+                assert_eq!(pascal.get(n, k), checked_choose(n, k), "{} choose {}", n, k);
+            }
+        }
+    }
+    #[test]
+    fn test_multinomial_and_stars_and_bars() {
+        // The arrangements of MISSISSIPPI
+        assert_eq!(multinomial(&[1, 4, 4, 2]), Some(34650));
+        assert_eq!(multinomial(&[]), Some(1));
+        assert_eq!(multinomial(&[3, 2]), Some(choose(5, 2)));
+        assert_eq!(multinomial(&[100, 100, 100]), None);
+        assert_eq!(stars_and_bars(3, 4), Some(20));
+        assert_eq!(stars_and_bars(0, 0), Some(1));
+        assert_eq!(stars_and_bars(1, 0), Some(0));
+    }
+    #[test]
+    fn test_permutations() {
+        let all: Vec<Vec<char>> = permutations(&['a', 'b', 'c']).collect();
+        assert_eq!(
+            all,
+            vec![
+                vec!['a', 'b', 'c'],
+                vec!['a', 'c', 'b'],
+                vec!['b', 'a', 'c'],
+                vec!['b', 'c', 'a'],
+                vec!['c', 'a', 'b'],
+                vec!['c', 'b', 'a'],
+            ]
+        );
+        assert_eq!(permutations(&[0; 0]).count(), 1);
+        assert_eq!(
+            permutations(&[1, 2, 3, 4, 5, 6]).count() as u128,
+            factorial(6).unwrap()
+        );
+    }
+    #[test]
+    fn test_combinations() {
+        let pairs: Vec<Vec<usize>> = combinations(&[1, 2, 3, 4], 2).collect();
+        assert_eq!(
+            pairs,
+            vec![
+                vec![1, 2],
+                vec![1, 3],
+                vec![1, 4],
+                vec![2, 3],
+                vec![2, 4],
+                vec![3, 4]
+            ]
+        );
+        assert_eq!(combinations(&[1, 2], 0).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(combinations(&[1, 2], 3).count(), 0);
+        assert_eq!(combinations(&[0; 10], 4).count() as u128, choose(10, 4));
+    }
+}
+
+/// n!, or `None` if it's too big for a u128.
+pub fn factorial(n: u64) -> Option<u128> {
+    (1..=n as u128).try_fold(1u128, |product, i| product.checked_mul(i))
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+/// The number of ways to choose `k` of `n` things, or `None` if it's too big
+/// for a u128.
+pub fn checked_choose(n: u64, k: u64) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let k = std::cmp::min(k, n - k);
+    let mut result = 1u128;
+    for i in 0..k as u128 {
+        // result * (n - i) is always divisible by i + 1, since it's
+        // (n choose i + 1) * (i + 1). Dividing out the common factor first keeps
+        // the multiplication from overflowing before the answer does.
+        let numerator = n as u128 - i;
+        let denominator = i + 1;
+        let common = gcd(result, denominator);
+        result = (result / common).checked_mul(numerator / (denominator / common))?;
+    }
+    Some(result)
+}
+
+/// The number of ways to choose `k` of `n` things.
+///
+/// Panics if it's too big for a u128.
+pub fn choose(n: u64, k: u64) -> u128 {
+    checked_choose(n, k).unwrap_or_else(|| panic!("{} choose {} overflows a u128", n, k))
+}
+
+/// Binomial coefficients up to some `n`, worked out by adding, for when lots
+/// of them are needed.
+#[derive(Clone, Debug)]
+pub struct Pascal {
+    /// Row `n` holds `n choose k` for each `k` up to `n`, or `None` where it
+    /// overflows
+    rows: Vec<Vec<Option<u128>>>,
+}
+
+impl Pascal {
+    /// The triangle with rows `0..=max_n`
+    pub fn new(max_n: u64) -> Pascal {
+        let mut rows: Vec<Vec<Option<u128>>> = vec![vec![Some(1)]];
+        for n in 1..=max_n as usize {
+            let above = &rows[n - 1];
+            let mut row = vec![Some(1); n + 1];
+            for k in 1..n {
+                row[k] = match (above[k - 1], above[k]) {
+                    (Some(left), Some(right)) => left.checked_add(right),
+                    _ => None,
+                };
+            }
+            rows.push(row);
+        }
+        Pascal { rows }
+    }
+    /// `n choose k`, or `None` if it overflows or `n` is past the triangle
+    pub fn get(&self, n: u64, k: u64) -> Option<u128> {
+        let row = self.rows.get(n as usize)?;
+        if k > n {
+            return Some(0);
+        }
+        row[k as usize]
+    }
+}
+
+/// The number of ways to split `counts.iter().sum()` things into groups of
+/// each count, or `None` if it's too big for a u128.
+pub fn multinomial(counts: &[u64]) -> Option<u128> {
+    let mut total = 0u64;
+    let mut result = 1u128;
+    for &count in counts {
+        total = total.checked_add(count)?;
+        result = result.checked_mul(checked_choose(total, count)?)?;
+    }
+    Some(result)
+}
+
+/// The number of ways to put `stars` identical things into `bins` bins, or
+/// equivalently, to choose `stars` things from `bins` kinds with repetition.
+/// `None` if it's too big for a u128.
+pub fn stars_and_bars(stars: u64, bins: u64) -> Option<u128> {
+    if bins == 0 {
+        return Some((stars == 0) as u128);
+    }
+    checked_choose(stars.checked_add(bins - 1)?, bins - 1)
+}
+
+/// Every ordering of `items`, in lexicographic order of their positions.
+pub fn permutations<T: Clone>(items: &[T]) -> Permutations<'_, T> {
+    Permutations {
+        items,
+        indices: (0..items.len()).collect(),
+        done: false,
+    }
+}
+
+/// An iterator over orderings of a slice, see `permutations`.
+#[derive(Clone, Debug)]
+pub struct Permutations<'a, T> {
+    items: &'a [T],
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<'a, T: Clone> Iterator for Permutations<'a, T> {
+    type Item = Vec<T>;
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let permutation = self
+            .indices
+            .iter()
+            .map(|&i| self.items[i].clone())
+            .collect();
+        // Step to the next permutation: find the last ascent, swap its first
+        // half with the smallest bigger index after it, and reverse the rest
+        let indices = &mut self.indices;
+        match (1..indices.len())
+            .rev()
+            .find(|&i| indices[i - 1] < indices[i])
+        {
+            None => self.done = true,
+            Some(i) => {
+                let j = (i..indices.len())
+                    .rev()
+                    .find(|&j| indices[j] > indices[i - 1])
+                    .unwrap();
+                indices.swap(i - 1, j);
+                indices[i..].reverse();
+            }
+        }
+        Some(permutation)
+    }
+}
+
+/// Every way to choose `k` of `items`, keeping their order, in lexicographic
+/// order of their positions.
+pub fn combinations<T: Clone>(items: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations {
+        items,
+        indices: (0..k).collect(),
+        done: k > items.len(),
+    }
+}
+
+/// An iterator over subsets of a slice, see `combinations`.
+#[derive(Clone, Debug)]
+pub struct Combinations<'a, T> {
+    items: &'a [T],
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<'a, T: Clone> Iterator for Combinations<'a, T> {
+    type Item = Vec<T>;
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let combination = self
+            .indices
+            .iter()
+            .map(|&i| self.items[i].clone())
+            .collect();
+        // Step to the next combination: bump the last index that can still
+        // move right, and put the ones after it right after it
+        let (n, k) = (self.items.len(), self.indices.len());
+        match (0..k).rev().find(|&i| self.indices[i] < n - k + i) {
+            None => self.done = true,
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in i + 1..k {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+        }
+        Some(combination)
+    }
+}
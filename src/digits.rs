@@ -0,0 +1,220 @@
+// Working with the digits of numbers, in any base, without allocating
+
+#[cfg(test)]
+mod tests {
+    use crate::digits::*;
+    #[test]
+    fn test_digits() {
+        assert_eq!(digits(1234, 10).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(digits(0, 10).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(digits(105, 10).collect::<Vec<_>>(), vec![1, 0, 5]);
+        assert_eq!(digits(105, 10).rev().collect::<Vec<_>>(), vec![5, 0, 1]);
+        assert_eq!(digits(0b1101, 2).collect::<Vec<_>>(), vec![1, 1, 0, 1]);
+        assert_eq!(digits(0xff, 16).collect::<Vec<_>>(), vec![15, 15]);
+        assert_eq!(digits(u64::MAX, 10).len(), 20);
+        assert_eq!(digits(u64::MAX, 2).len(), 64);
+        // Taking from both ends meets in the middle
+        let mut both = digits(12345, 10);
+        assert_eq!(
+            (both.next(), both.next_back(), both.next(), both.next_back()),
+            (Some(1), Some(5), Some(2), Some(4))
+        );
+        assert_eq!(
+            (both.len(), both.next(), both.next_back()),
+            (1, Some(3), None)
+        );
+    }
+    #[test]
+    fn test_from_digits() {
+        assert_eq!(from_digits(vec![1, 2, 3, 4], 10), Some(1234));
+        assert_eq!(from_digits(vec![0, 0, 7], 10), Some(7));
+        assert_eq!(from_digits(vec![1, 1, 0, 1], 2), Some(13));
+        assert_eq!(from_digits(digits(u64::MAX, 7), 7), Some(u64::MAX));
+        assert_eq!(from_digits(vec![9; 20], 10), None);
+    }
+    #[test]
+    fn test_reverse_and_palindromes() {
+        assert_eq!(reverse(1230, 10), Some(321));
+        assert_eq!(reverse(0b110, 2), Some(0b011));
+        assert_eq!(reverse(u64::MAX, 10), None);
+        assert!(is_palindrome(12321, 10) && is_palindrome(0, 10) && is_palindrome(0b101, 2));
+        assert!(!is_palindrome(1232, 10) && !is_palindrome(10, 10));
+    }
+    #[test]
+    fn test_digit_sum() {
+        assert_eq!(digit_sum(1234, 10), 10);
+        assert_eq!(digit_sum(0xff, 16), 30);
+        assert_eq!(digit_sum(u64::MAX, 2), 64);
+        assert_eq!(count(0, 10), 1);
+        assert_eq!(count(999, 10), 3);
+    }
+    #[test]
+    fn test_non_decreasing() {
+        assert_eq!(next_non_decreasing(123, 10), Some(123));
+        assert_eq!(next_non_decreasing(213, 10), Some(222));
+        assert_eq!(next_non_decreasing(321, 10), Some(333));
+        assert_eq!(next_non_decreasing(165432, 10), Some(166666));
+        assert_eq!(next_non_decreasing(0b10, 2), Some(0b11));
+        assert_eq!(next_non_decreasing(u64::MAX, 10), None);
+        assert_eq!(previous_non_decreasing(321, 10), 299);
+        assert_eq!(previous_non_decreasing(1320, 10), 1299);
+        assert_eq!(previous_non_decreasing(1110, 10), 999);
+        assert_eq!(previous_non_decreasing(707912, 10), 699999);
+        assert_eq!(previous_non_decreasing(5, 10), 5);
+        // Both agree with checking every number
+        let is_non_decreasing = |n: u64| {
+            digits(n, 10)
+                .zip(digits(n, 10).skip(1))
+                .all(|(a, b)| a <= b)
+        };
+        for n in 0..2000 {
+            let next = (n..).find(|&m| is_non_decreasing(m));
+            let previous = (0..=n).rev().find(|&m| is_non_decreasing(m)).unwrap();
+            assert_eq!(next_non_decreasing(n, 10), next, "{}", n);
+            assert_eq!(previous_non_decreasing(n, 10), previous, "{}", n);
+        }
+    }
+}
+
+/// The digits of `number` in `base`, most significant first. Zero has one
+/// digit. Panics if `base` is less than 2.
+pub fn digits(number: u64, base: u64) -> Digits {
+    assert!(base >= 2, "base must be at least 2, not {}", base);
+    // The place of the most significant digit
+    let mut place = 1;
+    let mut len = 1;
+    while place <= number / base {
+        place *= base;
+        len += 1;
+    }
+    Digits {
+        number,
+        place,
+        base,
+        len,
+    }
+}
+
+/// An iterator over the digits of a number, see `digits`.
+#[derive(Clone, Debug)]
+pub struct Digits {
+    /// The digits that haven't been taken yet
+    number: u64,
+    /// The place of the most significant digit left
+    place: u64,
+    base: u64,
+    len: usize,
+}
+
+impl Iterator for Digits {
+    type Item = u64;
+    fn next(&mut self) -> Option<u64> {
+        if self.len == 0 {
+            return None;
+        }
+        let digit = self.number / self.place;
+        self.number %= self.place;
+        self.place /= self.base;
+        self.len -= 1;
+        Some(digit)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl DoubleEndedIterator for Digits {
+    fn next_back(&mut self) -> Option<u64> {
+        if self.len == 0 {
+            return None;
+        }
+        let digit = self.number % self.base;
+        self.number /= self.base;
+        self.place /= self.base;
+        self.len -= 1;
+        Some(digit)
+    }
+}
+
+impl ExactSizeIterator for Digits {}
+
+/// The number with `digits` in `base`, most significant first, or `None` if
+/// it's too big for a u64.
+pub fn from_digits<I: IntoIterator<Item = u64>>(digits: I, base: u64) -> Option<u64> {
+    digits.into_iter().try_fold(0u64, |number, digit| {
+        number.checked_mul(base)?.checked_add(digit)
+    })
+}
+
+/// The number of digits `number` has in `base`.
+pub fn count(number: u64, base: u64) -> usize {
+    digits(number, base).len()
+}
+
+/// The sum of the digits of `number` in `base`.
+pub fn digit_sum(number: u64, base: u64) -> u64 {
+    digits(number, base).sum()
+}
+
+/// `number` with its digits in `base` backwards, or `None` if that's too big
+/// for a u64. Trailing zeros become leading zeros, and so disappear.
+pub fn reverse(number: u64, base: u64) -> Option<u64> {
+    from_digits(digits(number, base).rev(), base)
+}
+
+/// Whether `number` reads the same backwards in `base`.
+pub fn is_palindrome(number: u64, base: u64) -> bool {
+    let mut digits = digits(number, base);
+    while let (Some(front), Some(back)) = (digits.next(), digits.next_back()) {
+        if front != back {
+            return false;
+        }
+    }
+    true
+}
+
+/// The smallest number at least `number` whose digits in `base` never
+/// decrease, or `None` if that's too big for a u64.
+pub fn next_non_decreasing(number: u64, base: u64) -> Option<u64> {
+    // Once a digit is smaller than the one before, the smallest fix is to
+    // repeat the one before for the rest of the number
+    let mut previous = 0;
+    let mut fill = false;
+    from_digits(
+        digits(number, base).map(|digit| {
+            if fill || digit < previous {
+                fill = true;
+            } else {
+                previous = digit;
+            }
+            previous
+        }),
+        base,
+    )
+}
+
+/// The largest number at most `number` whose digits in `base` never decrease.
+pub fn previous_non_decreasing(number: u64, base: u64) -> u64 {
+    let mut result = 0;
+    let mut previous = 0;
+    // Where the current run of equal digits started: its place, and the
+    // result before it
+    let mut run = (0, 0);
+    let mut place = digits(number, base).place;
+    for digit in digits(number, base) {
+        if digit < previous {
+            // Lower the first digit of the run before this digit by one and
+            // fill everything after it with the biggest digit. The digit before
+            // the run was smaller, so that still doesn't decrease.
+            let (run_place, before) = run;
+            return before + (previous - 1) * run_place + (run_place - 1);
+        }
+        if digit != previous {
+            run = (place, result);
+        }
+        result += digit * place;
+        previous = digit;
+        place /= base;
+    }
+    number
+}
@@ -0,0 +1,163 @@
+// Puzzle examples kept as files, checked against the registered solutions
+//
+// A fixture lives at `fixtures/<year>/day<NN>/<name>.txt`. It starts with the
+// expected answers, one `part<N>: <answer>` line per part it covers, then a
+// `---` line, then the puzzle input exactly as given:
+//
+//     part1: 159
+//     part2: 610
+//     ---
+//     R75,D30,R83,U83,L12,D49,R71,U7,L72
+//     U62,R66,U55,R34,D71,R55,D58,R83
+//
+// Windows line endings are read as plain newlines. A fixture without any
+// answers yet, like a stub from `aoc new-day`, only checks that its input
+// parses. Every fixture becomes its own test in
+// `tests/fixtures.rs`.
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture::*;
+    #[test]
+    fn test_parse() {
+        let fixture = Fixture::parse(
+            "fixtures/2019/day01/example.txt",
+            "# From the puzzle\npart1: 2\n\n---\n12\n",
+        )
+        .unwrap();
+        assert_eq!((fixture.year, fixture.day), (2019, 1));
+        assert_eq!(fixture.name, "example");
+        assert_eq!(fixture.expected, vec![(Part::One, "2".to_string())]);
+        assert_eq!(fixture.input, "12\n");
+        let stub = Fixture::parse("fixtures/2019/day04/stub.txt", "# part1:\n---\n").unwrap();
+        assert!(stub.expected.is_empty());
+        assert_eq!(stub.input, "");
+        let crlf = Fixture::parse(
+            "fixtures/2019/day01/example.txt",
+            "# From the puzzle\r\npart1: 2\r\n---\r\n12\r\n",
+        );
+        assert_eq!(crlf, Ok(fixture));
+    }
+    #[test]
+    fn test_parse_errors() {
+        let path = "fixtures/2019/day01/example.txt";
+        assert!(Fixture::parse(path, "part1: 2\n12\n").is_err());
+        assert!(Fixture::parse(path, "part3: 2\n---\n12\n").is_err());
+        assert!(Fixture::parse(path, "part1 2\n---\n12\n").is_err());
+        assert!(Fixture::parse("fixtures/day01/example.txt", "part1: 2\n---\n").is_err());
+        assert!(Fixture::parse("fixtures/2019/one/example.txt", "part1: 2\n---\n").is_err());
+    }
+    #[test]
+    fn test_check() {
+        let path = "fixtures/2019/day04/range.txt";
+        let passing = Fixture::parse(path, "part1: 1716\npart2: 1163\n---\n165432-707912\n");
+        assert_eq!(passing.unwrap().check(), Ok(()));
+        let failing = Fixture::parse(path, "part2: 1\n---\n165432-707912\n").unwrap();
+        assert!(failing.check().unwrap_err().contains("part 2"));
+        let missing = Fixture::parse("fixtures/2019/day25/a.txt", "part1: 1\n---\n").unwrap();
+        assert!(missing.check().is_err());
+    }
+}
+
+use crate::solution::{self, Part};
+use std::fs;
+use std::path::Path;
+
+mod walk;
+pub use self::walk::paths;
+
+/// An example input and the answers it should give.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fixture {
+    pub year: u32,
+    pub day: u32,
+    /// The file name, without `.txt`
+    pub name: String,
+    /// The answers for the parts this example covers
+    pub expected: Vec<(Part, String)>,
+    pub input: String,
+}
+
+/// The number in a path component like `day04`, or `2019` with no prefix.
+fn number_after(component: Option<&str>, prefix: &str) -> Option<u32> {
+    component?.strip_prefix(prefix)?.parse().ok()
+}
+
+impl Fixture {
+    /// Reads the fixture at `path`
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Fixture, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|error| format!("couldn't read {}: {}", path.display(), error))?;
+        Fixture::parse(path, &text)
+    }
+    /// Parses `text` as the fixture at `path`, which gives its year and day
+    pub fn parse<P: AsRef<Path>>(path: P, text: &str) -> Result<Fixture, String> {
+        let path = path.as_ref();
+        let bad = |what: String| format!("{}: {}", path.display(), what);
+        let components: Vec<&str> = path
+            .iter()
+            .rev()
+            .take(3)
+            .filter_map(|component| component.to_str())
+            .collect();
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| bad("no file name".to_string()))?;
+        let day = number_after(components.get(1).copied(), "day")
+            .ok_or_else(|| bad("expected to be in a dayNN directory".to_string()))?;
+        let year = number_after(components.get(2).copied(), "")
+            .ok_or_else(|| bad("expected to be in a year directory".to_string()))?;
+
+        let text = text.replace("\r\n", "\n");
+        let (header, input) = match text.strip_prefix("---\n") {
+            Some(input) => ("", input),
+            None => match text.find("\n---\n") {
+                Some(i) => (&text[..i], &text[i + "\n---\n".len()..]),
+                None => return Err(bad("no --- line before the input".to_string())),
+            },
+        };
+        let mut expected = vec![];
+        for line in header.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, answer) = line
+                .split_once(':')
+                .ok_or_else(|| bad(format!("expected part<N>: <answer>, found {:?}", line)))?;
+            let part = match key.trim() {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                key => return Err(bad(format!("unknown part {:?}", key))),
+            };
+            expected.push((part, answer.trim().to_string()));
+        }
+        Ok(Fixture {
+            year,
+            day,
+            name: name.to_string(),
+            expected,
+            input: input.to_string(),
+        })
+    }
+    /// Runs the registered solution on the input, and checks its answers
+    pub fn check(&self) -> Result<(), String> {
+        let day = solution::find(self.year, self.day)
+            .ok_or_else(|| format!("no solution for {} day {}", self.year, self.day))?;
+        let parsed = day.parse(&self.input)?;
+        for (part, expected) in &self.expected {
+            match day.solve(&parsed, *part) {
+                Ok(ref answer) if answer == expected => {}
+                Ok(answer) => {
+                    return Err(format!(
+                        "part {}: expected {}, got {}",
+                        part, expected, answer
+                    ))
+                }
+                Err(error) => return Err(format!("part {}: {}", part, error)),
+            }
+        }
+        Ok(())
+    }
+}
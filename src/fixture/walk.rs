@@ -0,0 +1,27 @@
+// Finding fixture files, shared with build.rs so the generated tests and
+// `every_fixture_has_a_test` agree on what a fixture is
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The paths of every fixture under `root`, sorted.
+pub fn paths<P: AsRef<Path>>(root: P) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    let mut directories = vec![root.as_ref().to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        let entries = match fs::read_dir(&directory) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            if path.is_dir() {
+                directories.push(path);
+            } else if path.extension().is_some_and(|extension| extension == "txt") {
+                paths.push(path);
+            }
+        }
+    }
+    paths.sort();
+    paths
+}
@@ -0,0 +1,616 @@
+// 2D geometry shared by the grid puzzles
+//
+// Coordinates are y-down, like the lines of a puzzle input: `Direction::Up`
+// decreases y, and rotating left is counter-clockwise as drawn on the screen.
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::*;
+    #[test]
+    fn test_point_ops() {
+        let p = Point::new(1, 2);
+        let q = Point::new(3, -4);
+        assert_eq!(p + q, Point::new(4, -2));
+        assert_eq!(p - q, Point::new(-2, 6));
+        assert_eq!(p * 3, Point::new(3, 6));
+        assert_eq!(-p, Point::new(-1, -2));
+        let mut r = p;
+        r += q;
+        r -= Point::new(1, 1);
+        assert_eq!(r, Point::new(3, -3));
+    }
+    #[test]
+    fn test_directions() {
+        let p = Point::new(2, 2);
+        assert_eq!(p.step(Direction::Up, 3), Point::new(2, -1));
+        assert_eq!(p.step(Direction::Down, 1), Point::new(2, 3));
+        assert_eq!(p.step(Direction::Left, 1), Point::new(1, 2));
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        for direction in Direction::ALL.iter() {
+            assert_eq!(direction.turn_left().turn_right(), *direction);
+        }
+    }
+    #[test]
+    fn test_rotation() {
+        let p = Point::new(3, 1);
+        assert_eq!(p.rotate_left(), Point::new(1, -3));
+        assert_eq!(p.rotate_right(), Point::new(-1, 3));
+        assert_eq!(p.rotate_left().rotate_right(), p);
+        assert_eq!(p.rotate_left_around(Point::new(1, 1)), Point::new(1, -1));
+        for direction in Direction::ALL.iter() {
+            let offset: Point = direction.offset();
+            assert_eq!(offset.rotate_left(), direction.turn_left().offset());
+        }
+    }
+    #[test]
+    fn test_neighbors() {
+        let p = Point::new(0, 0);
+        assert_eq!(p.neighbors4().len(), 4);
+        assert_eq!(p.neighbors8().len(), 8);
+        assert!(p.neighbors4().iter().all(|q| p.manhattan(*q) == 1));
+        assert!(p.neighbors8().iter().all(|q| p.chebyshev(*q) == 1));
+    }
+    #[test]
+    fn test_metrics() {
+        let p = Point::new(1, 1);
+        let q = Point::new(4, -3);
+        assert_eq!(p.manhattan(q), 7);
+        assert_eq!(p.chebyshev(q), 4);
+        assert_eq!(p.euclidean(q), 5.0);
+        assert_eq!(manhattan_distance(p, q), 7);
+        let p: Point<u8> = Point::new(1, 9);
+        assert_eq!(p.manhattan(Point::new(4, 2)), 10);
+    }
+    #[test]
+    fn test_bounding_box() {
+        let points = vec![Point::new(1, 5), Point::new(-2, 3), Point::new(4, -1)];
+        let bounds = BoundingBox::of(points).unwrap();
+        assert_eq!(bounds.min(), Point::new(-2, -1));
+        assert_eq!(bounds.max(), Point::new(4, 5));
+        assert_eq!(bounds.width(), 7);
+        assert_eq!(bounds.height(), 7);
+        assert!(bounds.contains(Point::new(0, 0)));
+        assert!(!bounds.contains(Point::new(5, 0)));
+        assert_eq!(BoundingBox::<isize>::of(vec![]), None);
+    }
+    #[test]
+    fn test_ordering() {
+        use std::collections::BTreeSet;
+        let points = vec![Point::new(0, 3), Point::new(-1, 0), Point::new(0, -2)];
+        let set: BTreeSet<Point> = points.iter().cloned().collect();
+        assert_eq!(
+            set.into_iter().collect::<Vec<_>>(),
+            vec![Point::new(-1, 0), Point::new(0, -2), Point::new(0, 3)]
+        );
+        let mut sorted = points.clone();
+        sorted.sort_by(by_manhattan_from(Point::origin()));
+        assert_eq!(
+            sorted,
+            vec![Point::new(-1, 0), Point::new(0, -2), Point::new(0, 3)]
+        );
+        sorted.sort_by(by_manhattan_from(Point::new(0, 4)));
+        assert_eq!(
+            sorted,
+            vec![Point::new(0, 3), Point::new(-1, 0), Point::new(0, -2)]
+        );
+        sorted.sort_by(by_reading_order);
+        assert_eq!(
+            sorted,
+            vec![Point::new(0, -2), Point::new(-1, 0), Point::new(0, 3)]
+        );
+        // The row above comes first, like it does in a grid
+        let above = Point::origin().step(Direction::Up, 1);
+        assert_eq!(
+            by_reading_order(&above, &Point::new(-5, 0)),
+            std::cmp::Ordering::Less
+        );
+    }
+    #[test]
+    fn test_line() {
+        let line = Line::new(Point::new(2, 0), Point::new(2, 4));
+        assert!(line.is_vertical());
+        assert_eq!(line.length(), 4);
+        assert!(line.contains_point(&Point::new(2, 3)));
+        assert!(!line.contains_point(&Point::new(3, 3)));
+        let other = Line::new(Point::new(0, 1), Point::new(5, 1));
+        assert_eq!(line.find_intersection(&other), Some(Point::new(2, 1)));
+        assert_eq!(other.find_intersection(&line), Some(Point::new(2, 1)));
+    }
+    #[test]
+    fn test_diagonal_line() {
+        let line = Line::new(Point::new(0, 0), Point::new(4, 4));
+        assert!(line.is_diagonal());
+        assert!(!Line::new(Point::new(1, 1), Point::new(1, 1)).is_diagonal());
+        assert_eq!(line.length(), 4);
+        assert!(line.contains_point(&Point::new(3, 3)));
+        assert!(!line.contains_point(&Point::new(3, 1)));
+        let vertical = Line::new(Point::new(2, 5), Point::new(2, -1));
+        assert_eq!(line.find_intersection(&vertical), Some(Point::new(2, 2)));
+        assert_eq!(vertical.find_intersection(&line), Some(Point::new(2, 2)));
+        // These cross between (1, 1), (2, 2), (1, 2), and (2, 1)
+        let other = Line::new(Point::new(0, 3), Point::new(3, 0));
+        assert_eq!(line.find_intersection(&other), None);
+        let other = Line::new(Point::new(0, 4), Point::new(4, 0));
+        assert_eq!(line.find_intersection(&other), Some(Point::new(2, 2)));
+        let overlapping = Line::new(Point::new(6, 6), Point::new(2, 2));
+        assert_eq!(line.find_intersection(&overlapping), Some(Point::new(2, 2)));
+        let parallel = Line::new(Point::new(1, 0), Point::new(5, 4));
+        assert_eq!(line.find_intersection(&parallel), None);
+    }
+}
+
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A number that can be used as a coordinate.
+pub trait Number:
+    Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    /// The absolute difference between two numbers, which doesn't overflow for
+    /// unsigned types.
+    fn abs_diff(self, other: Self) -> Self;
+    fn to_f64(self) -> f64;
+    /// Convert to an i128 for exact signed arithmetic, truncating floats
+    fn to_i128(self) -> i128;
+    fn from_i128(n: i128) -> Self;
+}
+
+macro_rules! impl_number {
+    ($zero:expr, $one:expr; $($t:ty)*) => {
+        $(
+            impl Number for $t {
+                const ZERO: Self = $zero;
+                const ONE: Self = $one;
+                fn abs_diff(self, other: Self) -> Self {
+                    if self > other {
+                        self - other
+                    } else {
+                        other - self
+                    }
+                }
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+                fn from_i128(n: i128) -> Self {
+                    n as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_number!(0, 1; i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+impl_number!(0.0, 1.0; f32 f64);
+
+fn max<T: Number>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+fn min<T: Number>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+/// A point on the plane.
+///
+/// Points are ordered lexicographically, by x and then y. Use
+/// `by_manhattan_from` or `by_reading_order` to sort them some other way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T = isize> {
+    x: T,
+    y: T,
+}
+
+impl<T: Number> Point<T> {
+    pub fn new(x: T, y: T) -> Point<T> {
+        Point { x, y }
+    }
+    pub fn origin() -> Point<T> {
+        Point::new(T::ZERO, T::ZERO)
+    }
+    pub fn x(&self) -> T {
+        self.x
+    }
+    pub fn y(&self) -> T {
+        self.y
+    }
+    pub fn to_tuple(self) -> (T, T) {
+        (self.x, self.y)
+    }
+    /// The Manhattan (taxicab) distance between two points
+    pub fn manhattan(&self, other: Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+    /// The Chebyshev (chessboard) distance between two points
+    pub fn chebyshev(&self, other: Self) -> T {
+        max(self.x.abs_diff(other.x), self.y.abs_diff(other.y))
+    }
+    /// The Euclidean (straight line) distance between two points
+    pub fn euclidean(&self, other: Self) -> f64 {
+        let dx = self.x.abs_diff(other.x).to_f64();
+        let dy = self.y.abs_diff(other.y).to_f64();
+        dx.hypot(dy)
+    }
+}
+
+impl<T: Number + Neg<Output = T>> Point<T> {
+    /// The point `distance` steps away in `direction`
+    pub fn step(&self, direction: Direction, distance: T) -> Point<T> {
+        *self + direction.offset() * distance
+    }
+    /// Rotate the point 90 degrees counter-clockwise around the origin
+    pub fn rotate_left(&self) -> Point<T> {
+        Point::new(self.y, -self.x)
+    }
+    /// Rotate the point 90 degrees clockwise around the origin
+    pub fn rotate_right(&self) -> Point<T> {
+        Point::new(-self.y, self.x)
+    }
+    /// Rotate the point 90 degrees counter-clockwise around `center`
+    pub fn rotate_left_around(&self, center: Point<T>) -> Point<T> {
+        (*self - center).rotate_left() + center
+    }
+    /// Rotate the point 90 degrees clockwise around `center`
+    pub fn rotate_right_around(&self, center: Point<T>) -> Point<T> {
+        (*self - center).rotate_right() + center
+    }
+    /// The orthogonally adjacent points, in the order of `Direction::ALL`
+    pub fn neighbors4(&self) -> [Point<T>; 4] {
+        let mut neighbors = [*self; 4];
+        for (neighbor, direction) in neighbors.iter_mut().zip(Direction::ALL.iter()) {
+            *neighbor += direction.offset();
+        }
+        neighbors
+    }
+    /// The orthogonally and diagonally adjacent points, clockwise from up
+    pub fn neighbors8(&self) -> [Point<T>; 8] {
+        let mut neighbors = [*self; 8];
+        for (i, direction) in Direction::ALL.iter().enumerate() {
+            let offset = direction.offset();
+            neighbors[2 * i] += offset;
+            // The diagonal clockwise from this direction
+            neighbors[2 * i + 1] += offset + direction.turn_right().offset();
+        }
+        neighbors
+    }
+}
+
+impl Point<isize> {
+    /// The Manhattan distance from the origin
+    pub fn distance(&self) -> usize {
+        manhattan_distance(Point::origin(), *self)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Point<T> {
+        Point { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> (T, T) {
+        (point.x, point.y)
+    }
+}
+
+impl<T: Number> Add for Point<T> {
+    type Output = Point<T>;
+    fn add(self, other: Self) -> Point<T> {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Number> Sub for Point<T> {
+    type Output = Point<T>;
+    fn sub(self, other: Self) -> Point<T> {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Number> Mul<T> for Point<T> {
+    type Output = Point<T>;
+    fn mul(self, scalar: T) -> Point<T> {
+        Point::new(self.x * scalar, self.y * scalar)
+    }
+}
+
+impl<T: Number + Neg<Output = T>> Neg for Point<T> {
+    type Output = Point<T>;
+    fn neg(self) -> Point<T> {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Number> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Number> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+/// Order points by Manhattan distance from `origin`, breaking ties by x and
+/// then y, e.g. `points.sort_by(by_manhattan_from(Point::origin()))`.
+pub fn by_manhattan_from<T: Number + Ord>(
+    origin: Point<T>,
+) -> impl Fn(&Point<T>, &Point<T>) -> Ordering {
+    move |p, q| {
+        p.manhattan(origin)
+            .cmp(&q.manhattan(origin))
+            .then_with(|| p.cmp(q))
+    }
+}
+
+/// Order points by y and then x, top to bottom and left to right, the order
+/// you would read a grid.
+pub fn by_reading_order<T: Ord>(p: &Point<T>, q: &Point<T>) -> Ordering {
+    p.y.cmp(&q.y).then_with(|| p.x.cmp(&q.x))
+}
+
+/// One of the four orthogonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All the directions, clockwise from up
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+    /// The direction 90 degrees counter-clockwise
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+    /// The direction 90 degrees clockwise
+    pub fn turn_right(self) -> Direction {
+        self.turn_left().opposite()
+    }
+    pub fn opposite(self) -> Direction {
+        self.turn_left().turn_left()
+    }
+    /// The point one step from the origin in this direction
+    pub fn offset<T: Number + Neg<Output = T>>(self) -> Point<T> {
+        match self {
+            Direction::Up => Point::new(T::ZERO, -T::ONE),
+            Direction::Right => Point::new(T::ONE, T::ZERO),
+            Direction::Down => Point::new(T::ZERO, T::ONE),
+            Direction::Left => Point::new(-T::ONE, T::ZERO),
+        }
+    }
+}
+
+/// The smallest axis-aligned rectangle containing a set of points, inclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoundingBox<T = isize> {
+    min: Point<T>,
+    max: Point<T>,
+}
+
+impl<T: Number> BoundingBox<T> {
+    /// The bounding box of a single point
+    pub fn new(point: Point<T>) -> BoundingBox<T> {
+        BoundingBox {
+            min: point,
+            max: point,
+        }
+    }
+    /// The bounding box of some points, or `None` if there aren't any
+    pub fn of<I: IntoIterator<Item = Point<T>>>(points: I) -> Option<BoundingBox<T>> {
+        let mut points = points.into_iter();
+        let mut bounds = BoundingBox::new(points.next()?);
+        points.for_each(|point| bounds.include(point));
+        Some(bounds)
+    }
+    /// Grow the bounding box to include `point`
+    pub fn include(&mut self, point: Point<T>) {
+        self.min = Point::new(min(self.min.x, point.x), min(self.min.y, point.y));
+        self.max = Point::new(max(self.max.x, point.x), max(self.max.y, point.y));
+    }
+    /// The corner with the smallest coordinates
+    pub fn min(&self) -> Point<T> {
+        self.min
+    }
+    /// The corner with the largest coordinates
+    pub fn max(&self) -> Point<T> {
+        self.max
+    }
+    /// The number of columns covered by the bounding box
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+    /// The number of rows covered by the bounding box
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+    pub fn contains(&self, point: Point<T>) -> bool {
+        self.min.x <= point.x
+            && point.x <= self.max.x
+            && self.min.y <= point.y
+            && point.y <= self.max.y
+    }
+}
+
+/// The vertical, horizontal, or diagonal (45 degree) line between two points
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Line<T = isize> {
+    p1: Point<T>,
+    p2: Point<T>,
+}
+
+impl<T: Number> Line<T> {
+    pub fn new(p1: Point<T>, p2: Point<T>) -> Line<T> {
+        Line { p1, p2 }
+    }
+    pub fn p1(&self) -> Point<T> {
+        self.p1
+    }
+    pub fn p2(&self) -> Point<T> {
+        self.p2
+    }
+    pub fn x_min(&self) -> T {
+        min(self.p1.x, self.p2.x)
+    }
+    pub fn x_max(&self) -> T {
+        max(self.p1.x, self.p2.x)
+    }
+    pub fn y_min(&self) -> T {
+        min(self.p1.y, self.p2.y)
+    }
+    pub fn y_max(&self) -> T {
+        max(self.p1.y, self.p2.y)
+    }
+    pub fn is_horizontal(&self) -> bool {
+        self.p1.y == self.p2.y
+    }
+    pub fn is_vertical(&self) -> bool {
+        self.p1.x == self.p2.x
+    }
+    pub fn is_diagonal(&self) -> bool {
+        self.p1.x.abs_diff(self.p2.x) == self.p1.y.abs_diff(self.p2.y)
+            && !(self.is_horizontal() && self.is_vertical())
+    }
+    pub fn bounding_box(&self) -> BoundingBox<T> {
+        let mut bounds = BoundingBox::new(self.p1);
+        bounds.include(self.p2);
+        bounds
+    }
+    pub fn find_intersection(&self, other: &Self) -> Option<Point<T>> {
+        if self.is_diagonal() || other.is_diagonal() {
+            return self.solve_intersection(other);
+        }
+        // Test the reverse if the first didn't find a point, because
+        // intersects only finds a if the first line is completely within the
+        // bounding box of the second line.
+        self.intersects(other).or_else(|| other.intersects(self))
+    }
+    fn intersects(&self, other: &Self) -> Option<Point<T>> {
+        if self.x_min() >= other.x_min()
+            && other.x_max() >= self.x_min()
+            && other.y_min() >= self.y_min()
+            && self.y_max() >= other.y_min()
+        {
+            return Some(Point::new(self.x_min(), other.y_min()));
+        }
+        None
+    }
+    /// The start of the line, the direction of each step along it (each
+    /// coordinate is -1, 0, or 1), and its number of steps, all as i128s.
+    fn steps(&self) -> ((i128, i128), (i128, i128), i128) {
+        let start = (self.p1.x.to_i128(), self.p1.y.to_i128());
+        let dx = self.p2.x.to_i128() - start.0;
+        let dy = self.p2.y.to_i128() - start.1;
+        (start, (dx.signum(), dy.signum()), dx.abs().max(dy.abs()))
+    }
+    /// The first point (from p1) where the lines cross, as the solution of
+    /// `p + a * s = q + b * t` for whole steps `s` and `t` along each line.
+    fn solve_intersection(&self, other: &Self) -> Option<Point<T>> {
+        let (p, a, n) = self.steps();
+        let (q, b, m) = other.steps();
+        let r = (q.0 - p.0, q.1 - p.1);
+        let det = a.1 * b.0 - a.0 * b.1;
+        let s = if det != 0 {
+            let s = b.0 * r.1 - b.1 * r.0;
+            let t = a.0 * r.1 - a.1 * r.0;
+            // Diagonals can cross between points, which doesn't count
+            if s % det != 0 || t % det != 0 {
+                return None;
+            }
+            let (s, t) = (s / det, t / det);
+            if s < 0 || s > n || t < 0 || t > m {
+                return None;
+            }
+            s
+        } else {
+            // Parallel lines only meet if they're on the same line, and then
+            // the first point of the overlap is the intersection
+            if a.0 * r.1 - a.1 * r.0 != 0 || (a == (0, 0) && r != (0, 0)) {
+                return None;
+            }
+            let along = |d: (i128, i128)| if a.0 != 0 { d.0 * a.0 } else { d.1 * a.1 };
+            let (first, last) = (along(r), along((r.0 + b.0 * m, r.1 + b.1 * m)));
+            let start = first.min(last).max(0);
+            if start > first.max(last).min(n) {
+                return None;
+            }
+            start
+        };
+        Some(Point::new(
+            T::from_i128(p.0 + a.0 * s),
+            T::from_i128(p.1 + a.1 * s),
+        ))
+    }
+    /// The length of the line, as the number of steps between adjacent points
+    pub fn length(&self) -> T {
+        // Since the line is either vertical, horizontal, or diagonal, Chebyshev
+        // distance is the number of steps
+        self.p1.chebyshev(self.p2)
+    }
+    pub fn contains_point(&self, point: &Point<T>) -> bool {
+        if self.is_diagonal() {
+            // The line goes from one corner of its bounding box to the
+            // opposite corner, so the points in the box equally far from p1 in
+            // x and y are on the line.
+            return self.bounding_box().contains(*point)
+                && point.x.abs_diff(self.p1.x) == point.y.abs_diff(self.p1.y);
+        }
+        // Case 1: Line is vertical so both x coords are equal. Contains point if point.x equals
+        // line.x and line.min_y <= point.y <= line.max_y.
+        //
+        // Case 2: Line is horizontal, so both y coords are equal. Contains point according to the
+        // above condition, swap x and y.
+        (point.x == self.p1.x && point.y <= self.y_max() && point.y >= self.y_min())
+            || (point.y == self.p1.y && point.x <= self.x_max() && point.x >= self.x_min())
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Debug for Line<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Line {{({}, {}) -> ({}, {})}}",
+            self.p1.x, self.p1.y, self.p2.x, self.p2.y
+        )
+    }
+}
+
+/// Calculate the manhattan distance between two points
+pub fn manhattan_distance<T, U>(p: T, q: U) -> usize
+where
+    T: Into<(isize, isize)>,
+    U: Into<(isize, isize)>,
+{
+    let p = p.into();
+    let q = q.into();
+    ((p.0 - q.0).abs() + (p.1 - q.1).abs()) as usize
+}
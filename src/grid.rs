@@ -0,0 +1,470 @@
+// Dense and sparse 2D grids indexed by points
+//
+// Rows are indexed by y growing downwards, like the lines of a puzzle input, so
+// `Point::new(x, y)` is column x of line y. That's the same way up as the rest
+// of `geometry`, so stepping `Direction::Up` goes to the line above.
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Point;
+    use crate::grid::*;
+    const INPUT: &str = "#..\n.#.\n##.\n#..\n";
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(INPUT).unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 4);
+        assert_eq!(grid[Point::new(1, 2)], '#');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.to_string(), INPUT);
+        assert_eq!(Grid::parse("#.\r\n.#\r"), Grid::parse("#.\n.#\n"));
+        assert_eq!(
+            Grid::parse("##\n#\n"),
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+        let digits = Grid::parse_with("12\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits.row(1), &[3, 4]);
+    }
+    #[test]
+    fn test_slicing() {
+        let grid = Grid::parse(INPUT).unwrap();
+        assert_eq!(grid.row(2), &['#', '#', '.']);
+        assert_eq!(grid.column(0).collect::<String>(), "#.##");
+        assert_eq!(grid.rows().count(), 4);
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid.iter().filter(|(_, &c)| c == '#').count(), 5);
+        let thin = Grid::new(0, 2, '.');
+        assert_eq!(thin.rows().collect::<Vec<_>>(), vec![&[] as &[char]; 2]);
+        assert_eq!(thin.to_string(), "\n\n");
+    }
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::parse(INPUT).unwrap();
+        let corner: Vec<Point> = grid.neighbors4(Point::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbors8(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8(Point::new(1, 1)).count(), 8);
+    }
+    #[test]
+    fn test_transformations() {
+        let grid = Grid::parse("ab\ncd\nef\n").unwrap();
+        assert_eq!(grid.rotate_right().to_string(), "eca\nfdb\n");
+        assert_eq!(grid.rotate_left().to_string(), "bdf\nace\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "ba\ndc\nfe\n");
+        assert_eq!(grid.flip_vertical().to_string(), "ef\ncd\nab\n");
+        assert_eq!(grid.transpose().to_string(), "ace\nbdf\n");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+    }
+    #[test]
+    fn test_sparse() {
+        let mut sparse = SparseGrid::new('.');
+        sparse.insert(Point::new(-1, 2), '#');
+        sparse.insert(Point::new(1, 0), '#');
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse[Point::new(-1, 2)], '#');
+        assert_eq!(sparse[Point::new(0, 0)], '.');
+        assert_eq!(sparse.get(Point::new(0, 0)), None);
+        assert_eq!(sparse.to_string(), "..#\n...\n#..\n");
+        let (dense, offset) = sparse.to_dense();
+        assert_eq!(offset, Point::new(-1, 0));
+        assert_eq!(dense.to_string(), sparse.to_string());
+        // Nothing set is an empty grid
+        let (empty, _) = SparseGrid::new('.').to_dense();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(empty.to_string(), "");
+        assert_eq!(empty.columns().count(), 0);
+        assert_eq!(
+            SparseGrid::from_dense(&Grid::parse(INPUT).unwrap(), |&c| c == '#').len(),
+            5
+        );
+    }
+}
+
+use crate::geometry::{BoundingBox, Point};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Why some text couldn't be parsed into a grid.
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// There were no lines
+    Empty,
+    /// A line (1-based) wasn't as long as the first line
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid has no rows"),
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} is {} cells long, but expected {}",
+                line, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// A rectangular grid stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid filled with `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+    /// Build a grid from its rows, which must all be the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, GridError> {
+        let width = rows.first().ok_or(GridError::Empty)?.len();
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (i, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::Ragged {
+                    line: i + 1,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+    /// Parse each non-empty line of `input` into a row, converting each
+    /// character into a cell with `f`. Carriage returns at the ends of lines
+    /// are line endings, not cells.
+    pub fn parse_with<F: FnMut(char) -> T>(input: &str, mut f: F) -> Result<Grid<T>, GridError> {
+        Grid::from_rows(
+            input
+                .lines()
+                .map(|line| line.trim_end_matches('\r'))
+                .filter(|line| !line.is_empty())
+                .map(|line| line.chars().map(&mut f).collect())
+                .collect(),
+        )
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// The index into `cells` of a point, if it is in the grid
+    fn index_of(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y() as usize * self.width + point.x() as usize)
+        } else {
+            None
+        }
+    }
+    /// The point at an index into `cells`
+    fn point_of(&self, index: usize) -> Point {
+        Point::new((index % self.width) as isize, (index / self.width) as isize)
+    }
+    pub fn contains(&self, point: Point) -> bool {
+        point.x() >= 0
+            && point.y() >= 0
+            && (point.x() as usize) < self.width
+            && (point.y() as usize) < self.height
+    }
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(move |i| &mut self.cells[i])
+    }
+    /// Every point in the grid, in reading order
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.cells.len()).map(move |i| self.point_of(i))
+    }
+    /// Every cell in the grid with its point, in reading order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (self.point_of(i), cell))
+    }
+    /// The orthogonal neighbors of `point` that are in the grid
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        IntoIterator::into_iter(point.neighbors4()).filter(move |&p| self.contains(p))
+    }
+    /// The orthogonal and diagonal neighbors of `point` that are in the grid
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        IntoIterator::into_iter(point.neighbors8()).filter(move |&p| self.contains(p))
+    }
+    /// The cells of row `y`
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+    /// The cells of column `x`, from top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| self.row(y))
+    }
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+    /// Apply `f` to every cell
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+    /// Build a `width` by `height` grid where each cell is copied from the
+    /// point of this grid given by `source`
+    fn rebuild<F: Fn(usize, usize) -> Point>(
+        &self,
+        width: usize,
+        height: usize,
+        source: F,
+    ) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(self[source(x, y)].clone());
+            }
+        }
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+    /// Rotate the grid 90 degrees clockwise
+    pub fn rotate_right(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rebuild(self.height, self.width, |x, y| {
+            Point::new(y as isize, (height - 1 - x) as isize)
+        })
+    }
+    /// Rotate the grid 90 degrees counter-clockwise
+    pub fn rotate_left(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rebuild(self.height, self.width, |x, y| {
+            Point::new((width - 1 - y) as isize, x as isize)
+        })
+    }
+    /// Mirror the grid left to right
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rebuild(self.width, self.height, |x, y| {
+            Point::new((width - 1 - x) as isize, y as isize)
+        })
+    }
+    /// Mirror the grid top to bottom
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rebuild(self.width, self.height, |x, y| {
+            Point::new(x as isize, (height - 1 - y) as isize)
+        })
+    }
+    /// Swap the rows and columns of the grid
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |x, y| {
+            Point::new(y as isize, x as isize)
+        })
+    }
+}
+
+impl Grid<char> {
+    /// Parse each non-empty line of `input` into a row of characters
+    pub fn parse(input: &str) -> Result<Grid<char>, GridError> {
+        Grid::parse_with(input, |c| c)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", point))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// An unbounded grid that only stores the cells that have been set. Every other
+/// cell is `default`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    default: T,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+            default,
+        }
+    }
+    /// The cells of a dense grid for which `keep` is true
+    pub fn from_dense<F: Fn(&T) -> bool>(grid: &Grid<T>, keep: F) -> SparseGrid<T>
+    where
+        T: Clone + Default,
+    {
+        let mut sparse = SparseGrid::new(T::default());
+        for (point, cell) in grid.iter().filter(|(_, cell)| keep(cell)) {
+            sparse.insert(point, cell.clone());
+        }
+        sparse
+    }
+    /// The number of cells that have been set
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+    /// The cell at `point`, if it has been set
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+    /// Set the cell at `point`, returning its previous value if it was set
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+    /// The cell at `point`, setting it with `f` if it hasn't been set
+    pub fn entry_or_insert_with<F: FnOnce() -> T>(&mut self, point: Point, f: F) -> &mut T {
+        self.cells.entry(point).or_insert_with(f)
+    }
+    /// Every set cell with its point, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(point, cell)| (*point, cell))
+    }
+    /// The set orthogonal neighbors of `point`
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        IntoIterator::into_iter(point.neighbors4()).filter(move |&p| self.contains(p))
+    }
+    /// The set orthogonal and diagonal neighbors of `point`
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        IntoIterator::into_iter(point.neighbors8()).filter(move |&p| self.contains(p))
+    }
+    /// The smallest box containing every set cell
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        BoundingBox::of(self.cells.keys().cloned())
+    }
+    /// Copy the set cells into a dense grid, filling the rest with `default`.
+    /// The point of the grid's top left corner is returned with it.
+    pub fn to_dense(&self) -> (Grid<T>, Point)
+    where
+        T: Clone,
+    {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return (Grid::new(0, 0, self.default.clone()), Point::origin()),
+        };
+        let mut grid = Grid::new(
+            bounds.width() as usize,
+            bounds.height() as usize,
+            self.default.clone(),
+        );
+        for (point, cell) in self.iter() {
+            grid[point - bounds.min()] = cell.clone();
+        }
+        (grid, bounds.min())
+    }
+}
+
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+    fn index(&self, point: Point) -> &T {
+        self.cells.get(&point).unwrap_or(&self.default)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(bounds) = self.bounds() {
+            for y in bounds.min().y()..=bounds.max().y() {
+                for x in bounds.min().x()..=bounds.max().x() {
+                    write!(f, "{}", self[Point::new(x, y)])?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
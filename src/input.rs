@@ -3,14 +3,70 @@
 #[cfg(test)]
 mod tests {
     use crate::input;
+    use crate::input::*;
     #[test]
     fn input_read_file_to_string() {
         assert_eq!(input::read_file_to_string("input/test"), "Test file\n")
     }
+    #[test]
+    fn test_records() {
+        let source = "12\n\n# a comment\n  14 \n1969\r\n";
+        let parsed: Vec<Record<usize>> = records(source.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                Record { line: 1, value: 12 },
+                Record { line: 4, value: 14 },
+                Record {
+                    line: 5,
+                    value: 1969
+                },
+            ]
+        );
+    }
+    #[test]
+    fn test_records_errors() {
+        let source = "12\n  1x4\n-\n";
+        let mut parsed = records::<usize, _>(source.as_bytes());
+        assert_eq!(parsed.next().unwrap().unwrap().value, 12);
+        match parsed.next().unwrap() {
+            Err(RecordError::Parse(error)) => {
+                assert_eq!((error.line, error.column), (2, 4));
+                assert_eq!(error.token, "1x4");
+            }
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+        match parsed.next().unwrap() {
+            Err(RecordError::Parse(error)) => assert_eq!((error.line, error.column), (3, 1)),
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+        assert!(parsed.next().is_none());
+        // Columns count characters, not bytes
+        let mut parsed = records::<usize, _>("\u{3000}\u{a0}12é\n".as_bytes());
+        match parsed.next().unwrap() {
+            Err(RecordError::Parse(error)) => assert_eq!(error.column, 5),
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+    }
+    #[test]
+    fn test_records_comment_prefix() {
+        let source = "// header\n1\n#2\n";
+        let parsed: Vec<String> = records(source.as_bytes())
+            .comment_prefix("//")
+            .map(|record| record.unwrap().value)
+            .collect();
+        assert_eq!(parsed, vec!["1", "#2"]);
+    }
 }
 
+use std::fmt;
 use std::fs::File;
 use std::io::prelude::*;
+use std::io::{self, BufReader};
+use std::marker::PhantomData;
+use std::str::FromStr;
 
 /// Read file at `path` into a String.
 ///
@@ -21,3 +77,141 @@ pub fn read_file_to_string(path: &str) -> String {
     file.read_to_string(&mut contents).unwrap();
     contents
 }
+
+/// Open the puzzle input at `path`, or standard input if `stdin` is true.
+///
+/// Panics if there's a problem.
+pub fn open(path: &str, stdin: bool) -> Box<dyn Read> {
+    if stdin {
+        Box::new(io::stdin())
+    } else {
+        Box::new(File::open(path).unwrap_or_else(|e| panic!("Couldn't open {}: {}", path, e)))
+    }
+}
+
+/// A value parsed from one line of input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record<T> {
+    /// The 1-based line the value was on
+    pub line: usize,
+    pub value: T,
+}
+
+/// A line that couldn't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The 1-based line of the bad entry
+    pub line: usize,
+    /// The 1-based column of the bad entry. For numbers, this is the first
+    /// character that isn't a digit.
+    pub column: usize,
+    /// The entry that couldn't be parsed
+    pub token: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {} ({:?})",
+            self.line, self.column, self.message, self.token
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Why a record couldn't be read.
+#[derive(Debug)]
+pub enum RecordError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::Io(error) => write!(f, "{}", error),
+            RecordError::Parse(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+/// Parse each line of `source` into a `T`, one line at a time, so the whole
+/// input never has to be in memory. Blank lines and comments are skipped.
+pub fn records<T: FromStr, R: Read>(source: R) -> Records<BufReader<R>, T> {
+    Records {
+        reader: BufReader::new(source),
+        line: 0,
+        buffer: String::new(),
+        comment_prefix: "#",
+        marker: PhantomData,
+    }
+}
+
+/// An iterator of values parsed from lines of input, see `records`.
+pub struct Records<R, T> {
+    reader: R,
+    line: usize,
+    buffer: String,
+    comment_prefix: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<R, T> Records<R, T> {
+    /// Skip lines starting with `prefix` (after whitespace) instead of `#`
+    pub fn comment_prefix(mut self, prefix: &'static str) -> Records<R, T> {
+        self.comment_prefix = prefix;
+        self
+    }
+}
+
+/// The 0-based column, in characters, of the first character of `token` that
+/// can't be part of a number, or 0 if it looks like a number.
+fn bad_column(token: &str) -> usize {
+    token
+        .chars()
+        .enumerate()
+        .position(|(i, c)| !(c.is_ascii_digit() || (i == 0 && (c == '-' || c == '+'))))
+        .unwrap_or(0)
+}
+
+impl<R: BufRead, T: FromStr> Iterator for Records<R, T>
+where
+    T::Err: fmt::Display,
+{
+    type Item = Result<Record<T>, RecordError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.buffer.clear();
+            match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => return None,
+                Ok(_) => self.line += 1,
+                Err(error) => return Some(Err(RecordError::Io(error))),
+            }
+            let token = self.buffer.trim();
+            if token.is_empty() || token.starts_with(self.comment_prefix) {
+                continue;
+            }
+            return Some(match T::from_str(token) {
+                Ok(value) => Ok(Record {
+                    line: self.line,
+                    value,
+                }),
+                Err(error) => {
+                    let indent =
+                        self.buffer.chars().count() - self.buffer.trim_start().chars().count();
+                    Err(RecordError::Parse(ParseError {
+                        line: self.line,
+                        column: indent + bad_column(token) + 1,
+                        token: token.to_string(),
+                        message: error.to_string(),
+                    }))
+                }
+            });
+        }
+    }
+}
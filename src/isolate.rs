@@ -0,0 +1,277 @@
+// Running a part in a child process, so one that hangs or eats all the
+// memory can be stopped without taking the rest of a batch down with it
+//
+// The child writes its record with `report`, which the parent reads back with
+// `Finished::record`. Memory is checked by polling the child's resident set
+// size in /proc, so the cap only works on Linux. When showing `Level::Info`,
+// what the child writes to standard error is passed through as it comes, so
+// its progress can be seen.
+
+#[cfg(test)]
+mod tests {
+    use crate::isolate::*;
+    use crate::output;
+    use crate::solution;
+    fn finished(outcome: Outcome, stdout: &str) -> Finished {
+        Finished {
+            outcome,
+            stdout: stdout.to_string(),
+            stderr: "thread 'main' panicked at src/y2019/day02.rs\n".to_string(),
+            elapsed: Duration::from_millis(3),
+        }
+    }
+    #[test]
+    fn test_report() {
+        let day = solution::find(2019, 4).unwrap();
+        let record = output::solve_part(&day, "165432-707912", Part::One);
+        let read = finished(Outcome::Exited(true), &report(&record)).record(&day, Part::One);
+        assert_eq!(read, record);
+        // Answers can be any text, even several lines of it
+        let mut art = record.clone();
+        art.answer = Err("#..#\n.##.\n".to_string());
+        let read = finished(Outcome::Exited(true), &report(&art)).record(&day, Part::One);
+        assert_eq!(read.answer, art.answer);
+    }
+    #[test]
+    fn test_finished_record() {
+        let day = solution::find(2019, 2).unwrap();
+        let timeout = finished(Outcome::Timeout(Duration::from_secs(2)), "");
+        let record = timeout.record(&day, Part::Two);
+        assert_eq!(record.stopped, Some(Stopped::Timeout));
+        assert_eq!(record.answer, Err("Timeout after 2s".to_string()));
+        assert_eq!(record.solve, Duration::from_millis(3));
+        let oom = finished(Outcome::OutOfMemory(64 << 20), "");
+        let record = oom.record(&day, Part::Two);
+        assert_eq!(record.stopped, Some(Stopped::OutOfMemory));
+        assert_eq!(record.answer, Err("OOM, using 64 MB".to_string()));
+        let crashed = finished(Outcome::Exited(false), "").record(&day, Part::Two);
+        assert_eq!(crashed.stopped, None);
+        assert!(crashed.answer.unwrap_err().contains("panicked"));
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_run() {
+        let limits = Limits {
+            timeout: Duration::from_millis(200),
+            max_rss: Some(32 << 20),
+        };
+        let echo = run(Command::new("echo").arg("hi"), &limits).unwrap();
+        assert_eq!(
+            (echo.outcome, echo.stdout.as_str()),
+            (Outcome::Exited(true), "hi\n")
+        );
+        let sleep = run(Command::new("sleep").arg("5"), &limits).unwrap();
+        assert_eq!(sleep.outcome, Outcome::Timeout(limits.timeout));
+        assert!(sleep.elapsed < Duration::from_secs(5));
+        // `tail` keeps the whole "line" of zeros it's reading in memory
+        let limits = Limits {
+            timeout: Duration::from_secs(10),
+            ..limits
+        };
+        let tail = run(Command::new("tail").arg("/dev/zero"), &limits).unwrap();
+        assert!(matches!(tail.outcome, Outcome::OutOfMemory(rss) if rss > 32 << 20));
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_rss() {
+        assert!(rss(std::process::id()).is_some_and(|rss| rss > 0));
+    }
+}
+
+use crate::log::{self, Level};
+use crate::output::{Record, Stopped};
+use crate::solution::{Day, Part};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a part may run, and how much memory it may use.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    pub timeout: Duration,
+    /// The most resident memory in bytes, if there's a cap
+    pub max_rss: Option<u64>,
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            timeout: Duration::from_secs(60),
+            max_rss: None,
+        }
+    }
+}
+
+/// How a child process ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// It exited by itself, successfully or not
+    Exited(bool),
+    /// It was killed after running for this long
+    Timeout(Duration),
+    /// It was killed for using this many bytes
+    OutOfMemory(u64),
+}
+
+/// A child process that's done, and what it printed.
+#[derive(Clone, Debug)]
+pub struct Finished {
+    pub outcome: Outcome,
+    pub stdout: String,
+    pub stderr: String,
+    pub elapsed: Duration,
+}
+
+/// The resident set size of process `pid` in bytes, if it can be read.
+pub fn rss(pid: u32) -> Option<u64> {
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Reads all of `pipe` on another thread, so a chatty child can't fill it
+/// up and block while it's being waited on. What's read is also written to
+/// `echo`, if there is one.
+fn drain<R, W>(pipe: Option<R>, mut echo: Option<W>) -> thread::JoinHandle<String>
+where
+    R: Read + Send + 'static,
+    W: Write + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut bytes = vec![];
+        let mut buffer = [0; 4096];
+        if let Some(mut pipe) = pipe {
+            while let Ok(read @ 1..) = pipe.read(&mut buffer) {
+                bytes.extend_from_slice(&buffer[..read]);
+                if let Some(echo) = &mut echo {
+                    echo.write_all(&buffer[..read]).ok();
+                    echo.flush().ok();
+                }
+            }
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+/// Kills `child`, and waits for it so it isn't left a zombie.
+fn kill(child: &mut Child) -> io::Result<()> {
+    child.kill()?;
+    child.wait().map(|_| ())
+}
+
+/// Runs `command`, killing it if it goes past `limits`.
+pub fn run(command: &mut Command, limits: &Limits) -> io::Result<Finished> {
+    let start = Instant::now();
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = drain(child.stdout.take(), None::<io::Stderr>);
+    let echo = if log::enabled(Level::Info) {
+        Some(io::stderr())
+    } else {
+        None
+    };
+    let stderr = drain(child.stderr.take(), echo);
+    let outcome = loop {
+        if let Some(status) = child.try_wait()? {
+            break Outcome::Exited(status.success());
+        }
+        if start.elapsed() > limits.timeout {
+            kill(&mut child)?;
+            break Outcome::Timeout(limits.timeout);
+        }
+        if let Some(max) = limits.max_rss {
+            if let Some(rss) = rss(child.id()).filter(|&rss| rss > max) {
+                kill(&mut child)?;
+                break Outcome::OutOfMemory(rss);
+            }
+        }
+        thread::sleep(Duration::from_millis(5));
+    };
+    let elapsed = start.elapsed();
+    Ok(Finished {
+        outcome,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+        elapsed,
+    })
+}
+
+/// `record` for the parent to read back: whether it's an answer, the parse
+/// and solve times in nanoseconds, then the answer or error as is.
+pub fn report(record: &Record) -> String {
+    let (status, text) = match &record.answer {
+        Ok(answer) => ("ok", answer),
+        Err(error) => ("error", error),
+    };
+    format!(
+        "{}\n{}\n{}\n{}",
+        status,
+        record.parse.as_nanos(),
+        record.solve.as_nanos(),
+        text
+    )
+}
+
+impl Finished {
+    /// The record for `part` of `day`, from the child's report, or saying
+    /// why there isn't one
+    pub fn record(&self, day: &Day, part: Part) -> Record {
+        let mut record = Record {
+            year: day.year,
+            day: day.day,
+            part,
+            variant: None,
+            answer: Err(String::new()),
+            stopped: None,
+            parse: Duration::ZERO,
+            solve: self.elapsed,
+        };
+        match self.outcome {
+            Outcome::Timeout(timeout) => {
+                record.stopped = Some(Stopped::Timeout);
+                record.answer = Err(format!("Timeout after {:?}", timeout));
+            }
+            Outcome::OutOfMemory(rss) => {
+                record.stopped = Some(Stopped::OutOfMemory);
+                record.answer = Err(format!("OOM, using {} MB", rss >> 20));
+            }
+            Outcome::Exited(success) => {
+                let mut lines = self.stdout.splitn(4, '\n');
+                let status = lines.next();
+                let mut nanos = || lines.next()?.parse().ok().map(Duration::from_nanos);
+                let (parse, solve) = (nanos(), nanos());
+                match (success, status, parse, solve, lines.next()) {
+                    (true, Some(status), Some(parse), Some(solve), Some(text)) => {
+                        record.parse = parse;
+                        record.solve = solve;
+                        record.answer = match status {
+                            "ok" => Ok(text.to_string()),
+                            _ => Err(text.to_string()),
+                        };
+                    }
+                    _ => {
+                        // Where a panic happened, and its message
+                        let why: Vec<&str> = self
+                            .stderr
+                            .lines()
+                            .filter(|line| !line.is_empty())
+                            .take(2)
+                            .collect();
+                        record.answer = if why.is_empty() {
+                            Err("crashed without saying why".to_string())
+                        } else {
+                            Err(format!("crashed: {}", why.join(" ")))
+                        };
+                    }
+                }
+            }
+        }
+        record
+    }
+}
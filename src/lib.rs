@@ -1,5 +1,28 @@
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
+// Tests live at the top of each module, before the code they test
+#![allow(clippy::items_after_test_module)]
+
+pub mod bench;
+pub mod bigint;
+pub mod combinatorics;
+pub mod digits;
+pub mod fixture;
+pub mod geometry;
+pub mod grid;
 pub mod input;
+pub mod isolate;
+pub mod log;
+pub mod output;
+pub mod parse;
+pub mod property;
+pub mod render;
+pub mod rng;
+pub mod scaffold;
+pub mod scan;
+pub mod search;
+pub mod solution;
+pub mod watch;
+pub mod y2019;
+// new-day: years
+
+// Where the 2019 days were before each year had its own module
+pub use y2019::{day01 as day1, day02 as day2, day03 as day3, day04 as day4};
@@ -0,0 +1,183 @@
+// Messages about what's going on, and progress through long searches, on
+// standard error
+//
+// Only warnings and errors are shown until a binary turns the level up, like
+// with `--verbose`, so tests and piped output stay quiet.
+
+#[cfg(test)]
+mod tests {
+    use crate::log::*;
+    #[test]
+    fn test_level_from_args() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(Level::from_args(&args(&["run"])), Level::Warn);
+        assert_eq!(Level::from_args(&args(&["run", "--verbose"])), Level::Info);
+        assert_eq!(Level::from_args(&args(&["-v", "--verbose"])), Level::Debug);
+        assert_eq!(Level::from_args(&args(&["-v", "-v", "-v"])), Level::Debug);
+        assert_eq!(Level::from_args(&args(&["--quiet"])), Level::Error);
+        assert!(Level::Error < Level::Debug);
+    }
+    #[test]
+    fn test_progress() {
+        // Nothing's shown at the default level
+        let progress = Progress::new("scan", 200);
+        progress.advance(50);
+        assert_eq!(progress.done(), 50);
+        assert_eq!(
+            progress.line(Duration::from_secs(2)),
+            "scan: 50/200 (25%) 2.0s"
+        );
+        progress.advance(500);
+        assert_eq!(progress.done(), 200);
+        assert_eq!(
+            Progress::new("empty", 0).line(Duration::ZERO),
+            "empty: 0/0 (100%) 0.0s"
+        );
+    }
+}
+
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How much to say, from only errors to everything.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    /// What's running, and progress through long searches
+    Info,
+    /// Details for tracking down problems
+    Debug,
+}
+
+impl Level {
+    const ALL: [Level; 4] = [Level::Error, Level::Warn, Level::Info, Level::Debug];
+    /// The level asked for in `args`: `--quiet` for only errors, or each
+    /// `--verbose` or `-v` for a level more than warnings
+    pub fn from_args(args: &[String]) -> Level {
+        if args.iter().any(|arg| arg == "--quiet") {
+            return Level::Error;
+        }
+        let verbose = args
+            .iter()
+            .filter(|arg| *arg == "--verbose" || *arg == "-v")
+            .count();
+        Level::ALL[std::cmp::min(Level::Warn as usize + verbose, Level::ALL.len() - 1)]
+    }
+    fn name(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+        }
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+/// Show messages at `level` and below from now on.
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Whether messages at `level` are being shown.
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+/// Writes `message` to standard error if `level` is being shown, see `log!`.
+pub fn log(level: Level, message: fmt::Arguments<'_>) {
+    if enabled(level) {
+        eprintln!("[{}] {}", level.name(), message);
+    }
+}
+
+/// Logs a message at a `Level`, formatted like `format!`:
+/// `log!(Info, "solving day {}", day)`.
+#[macro_export]
+macro_rules! log {
+    ($level:ident, $($arg:tt)*) => {
+        $crate::log::log($crate::log::Level::$level, format_args!($($arg)*))
+    };
+}
+
+/// How often progress is redrawn.
+const REDRAW: Duration = Duration::from_millis(100);
+
+/// Progress through `total` steps of a long search, redrawn on one line of
+/// standard error when showing `Level::Info`. It can be shared by threads.
+pub struct Progress {
+    label: String,
+    total: u64,
+    done: AtomicU64,
+    start: Instant,
+    /// When it was last drawn, if it's being shown at all
+    drawn: Option<Mutex<Instant>>,
+    /// Whether anything has been drawn, to finish the line
+    shown: AtomicBool,
+}
+
+impl Progress {
+    pub fn new(label: &str, total: u64) -> Progress {
+        let start = Instant::now();
+        Progress {
+            label: label.to_string(),
+            total,
+            done: AtomicU64::new(0),
+            start,
+            drawn: if enabled(Level::Info) {
+                Some(Mutex::new(start))
+            } else {
+                None
+            },
+            shown: AtomicBool::new(false),
+        }
+    }
+    /// How many steps are done
+    pub fn done(&self) -> u64 {
+        std::cmp::min(self.done.load(Ordering::Relaxed), self.total)
+    }
+    /// Marks `steps` more steps as done, redrawing if it's been a while
+    pub fn advance(&self, steps: u64) {
+        self.done.fetch_add(steps, Ordering::Relaxed);
+        let drawn = match &self.drawn {
+            Some(drawn) => drawn,
+            None => return,
+        };
+        // Whichever thread gets here first draws, the rest carry on
+        if let Ok(mut drawn) = drawn.try_lock() {
+            if drawn.elapsed() >= REDRAW {
+                *drawn = Instant::now();
+                self.shown.store(true, Ordering::Relaxed);
+                eprint!("\r{}", self.line(self.start.elapsed()));
+                io::stderr().flush().ok();
+            }
+        }
+    }
+    /// Draws the final count, if progress was shown
+    pub fn finish(&self) {
+        if self.shown.load(Ordering::Relaxed) {
+            eprintln!("\r{}", self.line(self.start.elapsed()));
+        }
+    }
+    /// The line showing how far along it is after `elapsed`
+    fn line(&self, elapsed: Duration) -> String {
+        let done = self.done();
+        let percent = match self.total {
+            0 => 100,
+            total => (u128::from(done) * 100 / u128::from(total)) as u64,
+        };
+        format!(
+            "{}: {}/{} ({}%) {:.1}s",
+            self.label,
+            done,
+            self.total,
+            percent,
+            elapsed.as_secs_f64()
+        )
+    }
+}
@@ -0,0 +1,379 @@
+// Reporting answers, for people or for other programs
+
+#[cfg(test)]
+mod tests {
+    use crate::output::*;
+    use crate::solution;
+    fn record(answer: Answer) -> Record {
+        Record {
+            year: 2019,
+            day: 4,
+            part: Part::One,
+            variant: None,
+            answer,
+            stopped: None,
+            parse: Duration::from_micros(2),
+            solve: Duration::from_micros(150),
+        }
+    }
+    fn written(format: Format, records: &[Record]) -> String {
+        let mut output = Output::new(format, vec![]);
+        for record in records {
+            output.record(record).unwrap();
+        }
+        String::from_utf8(output.into_inner()).unwrap()
+    }
+    #[test]
+    fn test_format_from_args() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(Format::from_args(&args(&["day4"])), Ok(Format::Text));
+        assert_eq!(
+            Format::from_args(&args(&["--format", "json"])),
+            Ok(Format::Json)
+        );
+        assert_eq!(Format::from_args(&args(&["--format=tsv"])), Ok(Format::Tsv));
+        assert!(Format::from_args(&args(&["--format", "xml"])).is_err());
+        assert!(Format::from_args(&args(&["--format"])).is_err());
+    }
+    #[test]
+    fn test_text() {
+        let mut brute = record(Ok("1716".to_string()));
+        brute.variant = Some("brute force");
+        assert_eq!(
+            written(Format::Text, &[record(Ok("1716".to_string())), brute]),
+            "2019 day 4 part 1: 1716 (parse 2.00µs, solve 150.00µs)\n\
+             2019 day 4 part 1 (brute force): 1716 (parse 2.00µs, solve 150.00µs)\n"
+        );
+        assert_eq!(
+            written(Format::Text, &[record(Err("no input".to_string()))]),
+            "2019 day 4 part 1 failed: no input\n"
+        );
+    }
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            written(Format::Json, &[record(Ok("1716".to_string()))]),
+            "{\"year\":2019,\"day\":4,\"part\":1,\"variant\":null,\"status\":\"ok\",\
+             \"answer\":\"1716\",\"error\":null,\"parse_ns\":2000,\"solve_ns\":150000}\n"
+        );
+        let failed = written(Format::Json, &[record(Err("bad \"input\"\n".to_string()))]);
+        assert!(failed
+            .contains("\"status\":\"error\",\"answer\":null,\"error\":\"bad \\\"input\\\"\\n\""));
+    }
+    #[test]
+    fn test_tsv() {
+        assert_eq!(
+            written(
+                Format::Tsv,
+                &[
+                    record(Ok("1716".to_string())),
+                    record(Err("a\tb".to_string()))
+                ]
+            ),
+            "year\tday\tpart\tvariant\tstatus\tanswer\tparse_ns\tsolve_ns\n\
+             2019\t4\t1\t\tok\t1716\t2000\t150000\n\
+             2019\t4\t1\t\terror\ta b\t2000\t150000\n"
+        );
+    }
+    #[test]
+    fn test_stopped() {
+        let mut stopped = record(Err("Timeout after 1s".to_string()));
+        stopped.stopped = Some(Stopped::Timeout);
+        assert_eq!(stopped.status(), "timeout");
+        assert!(written(Format::Tsv, &[stopped.clone()]).contains("\ttimeout\t"));
+        stopped.stopped = Some(Stopped::OutOfMemory);
+        assert_eq!(stopped.status(), "oom");
+    }
+    #[test]
+    fn test_solve() {
+        let day = solution::find(2019, 4).unwrap();
+        let records = solve(&day, "165432-707912");
+        let answers: Vec<_> = records.iter().map(|r| (r.part, r.answer.clone())).collect();
+        assert_eq!(
+            answers,
+            vec![
+                (Part::One, Ok("1716".to_string())),
+                (Part::Two, Ok("1163".to_string()))
+            ]
+        );
+        let records = solve(&day, "nope");
+        assert!(records.iter().all(|record| record.status() == "error"));
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            solve_part(&day, "165432-707912", Part::Two).answer,
+            Ok("1163".to_string())
+        );
+    }
+}
+
+use crate::solution::{Answer, Day, Parsed, Part};
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// How answers are written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// A line per answer, for reading
+    Text,
+    /// A JSON object per line
+    Json,
+    /// Tab separated, with a header line
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+    fn from_str(string: &str) -> Result<Format, String> {
+        match string {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!(
+                "unknown format {:?}, expected text, json, or tsv",
+                string
+            )),
+        }
+    }
+}
+
+impl Format {
+    /// The format picked by `--format <format>` or `--format=<format>` in
+    /// `args`, or text if there isn't one
+    pub fn from_args(args: &[String]) -> Result<Format, String> {
+        for (i, arg) in args.iter().enumerate() {
+            if let Some(format) = arg.strip_prefix("--format=") {
+                return format.parse();
+            }
+            if arg == "--format" {
+                return match args.get(i + 1) {
+                    Some(format) => format.parse(),
+                    None => Err("--format needs a format".to_string()),
+                };
+            }
+        }
+        Ok(Format::Text)
+    }
+}
+
+/// Why a part was stopped before it could answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stopped {
+    /// It ran for too long
+    Timeout,
+    /// It used too much memory
+    OutOfMemory,
+}
+
+/// One part's answer, and how long it took.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    /// Which way of solving the part this was, if a day has more than one
+    pub variant: Option<&'static str>,
+    pub answer: Answer,
+    /// If the part was stopped, why, with the answer saying more
+    pub stopped: Option<Stopped>,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Record {
+    /// Whether the part was answered, as `ok` or `error`, or `timeout` or
+    /// `oom` if it was stopped
+    pub fn status(&self) -> &'static str {
+        match (self.stopped, &self.answer) {
+            (Some(Stopped::Timeout), _) => "timeout",
+            (Some(Stopped::OutOfMemory), _) => "oom",
+            (None, Ok(_)) => "ok",
+            (None, Err(_)) => "error",
+        }
+    }
+}
+
+/// The record for `part`, given how parsing went and how long it took.
+fn answer(day: &Day, parsed: &Result<Parsed, String>, part: Part, parse: Duration) -> Record {
+    let start = Instant::now();
+    let answer = match parsed {
+        Ok(parsed) => day.solve(parsed, part),
+        Err(error) => Err(format!("couldn't parse {}: {}", day.input, error)),
+    };
+    Record {
+        year: day.year,
+        day: day.day,
+        part,
+        variant: None,
+        answer,
+        stopped: None,
+        parse,
+        solve: start.elapsed(),
+    }
+}
+
+/// Solves both parts of `day` for `input`, timing the parsing and each part.
+/// If the input can't be parsed, both parts fail with the reason.
+pub fn solve(day: &Day, input: &str) -> Vec<Record> {
+    let start = Instant::now();
+    let parsed = day.parse(input);
+    let parse = start.elapsed();
+    Part::ALL
+        .iter()
+        .map(|&part| answer(day, &parsed, part, parse))
+        .collect()
+}
+
+/// Solves just `part` of `day` for `input`, like `solve`.
+pub fn solve_part(day: &Day, input: &str, part: Part) -> Record {
+    let start = Instant::now();
+    let parsed = day.parse(input);
+    answer(day, &parsed, part, start.elapsed())
+}
+
+/// Both parts of `day` failing because of `error`, like a missing input.
+pub fn failed(day: &Day, error: &str) -> Vec<Record> {
+    Part::ALL
+        .iter()
+        .map(|&part| Record {
+            year: day.year,
+            day: day.day,
+            part,
+            variant: None,
+            answer: Err(error.to_string()),
+            stopped: None,
+            parse: Duration::ZERO,
+            solve: Duration::ZERO,
+        })
+        .collect()
+}
+
+/// Writes `string` as a JSON string, escaping as needed.
+fn json_string(string: &str) -> String {
+    let mut json = String::with_capacity(string.len() + 2);
+    json.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// A JSON string, or `null` for nothing.
+fn json_option(string: Option<&str>) -> String {
+    string.map_or_else(|| "null".to_string(), json_string)
+}
+
+/// `string` with anything that would break a TSV row turned into spaces.
+fn tsv_field(string: &str) -> String {
+    string.replace(['\t', '\n', '\r'], " ")
+}
+
+/// Where records go, and in what format.
+pub struct Output<W> {
+    format: Format,
+    writer: W,
+    /// Whether the TSV header has been written yet
+    started: bool,
+}
+
+impl Output<io::Stdout> {
+    /// Records in `format` on standard output
+    pub fn stdout(format: Format) -> Output<io::Stdout> {
+        Output::new(format, io::stdout())
+    }
+}
+
+impl<W: Write> Output<W> {
+    pub fn new(format: Format, writer: W) -> Output<W> {
+        Output {
+            format,
+            writer,
+            started: false,
+        }
+    }
+    pub fn format(&self) -> Format {
+        self.format
+    }
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+    pub fn record(&mut self, record: &Record) -> io::Result<()> {
+        match self.format {
+            Format::Text => self.text(record),
+            Format::Json => self.json(record),
+            Format::Tsv => self.tsv(record),
+        }
+    }
+    /// Writes each of `records`
+    pub fn records(&mut self, records: &[Record]) -> io::Result<()> {
+        records.iter().try_for_each(|record| self.record(record))
+    }
+    fn text(&mut self, record: &Record) -> io::Result<()> {
+        let variant = record
+            .variant
+            .map_or_else(String::new, |variant| format!(" ({})", variant));
+        let name = format!(
+            "{} day {} part {}{}",
+            record.year, record.day, record.part, variant
+        );
+        match &record.answer {
+            Ok(answer) => writeln!(
+                self.writer,
+                "{}: {} (parse {:.2?}, solve {:.2?})",
+                name, answer, record.parse, record.solve
+            ),
+            Err(error) => writeln!(self.writer, "{} failed: {}", name, error),
+        }
+    }
+    fn json(&mut self, record: &Record) -> io::Result<()> {
+        writeln!(
+            self.writer,
+            "{{\"year\":{},\"day\":{},\"part\":{},\"variant\":{},\"status\":{},\
+             \"answer\":{},\"error\":{},\"parse_ns\":{},\"solve_ns\":{}}}",
+            record.year,
+            record.day,
+            record.part,
+            json_option(record.variant),
+            json_string(record.status()),
+            json_option(record.answer.as_ref().ok().map(String::as_str)),
+            json_option(record.answer.as_ref().err().map(String::as_str)),
+            record.parse.as_nanos(),
+            record.solve.as_nanos()
+        )
+    }
+    fn tsv(&mut self, record: &Record) -> io::Result<()> {
+        if !self.started {
+            writeln!(
+                self.writer,
+                "year\tday\tpart\tvariant\tstatus\tanswer\tparse_ns\tsolve_ns"
+            )?;
+            self.started = true;
+        }
+        // Errors go in the answer column, the status says which it is
+        let answer = match &record.answer {
+            Ok(answer) => answer,
+            Err(error) => error,
+        };
+        writeln!(
+            self.writer,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            record.year,
+            record.day,
+            record.part,
+            record.variant.unwrap_or(""),
+            record.status(),
+            tsv_field(answer),
+            record.parse.as_nanos(),
+            record.solve.as_nanos()
+        )
+    }
+}
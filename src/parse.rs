@@ -0,0 +1,429 @@
+// Tiny parser combinators for puzzle inputs
+//
+// A `Parser` consumes some of an `Input` and either produces a value or a
+// `ParseError` with the span of the text it didn't expect.
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::*;
+    #[test]
+    fn test_integer() {
+        assert_eq!(integer::<u32>().parse("42"), Ok(42));
+        assert_eq!(integer::<i32>().parse("-7"), Ok(-7));
+        assert_eq!(integer::<i32>().trim().parse(" 8 \n"), Ok(8));
+        assert_eq!(
+            integer::<u32>().parse("x"),
+            Err(ParseError::new(Span::new(0, 1), "expected an integer"))
+        );
+        assert_eq!(
+            integer::<u8>().parse("300").unwrap_err().span,
+            Span::new(0, 3)
+        );
+        assert_eq!(
+            integer::<u32>().parse("-1"),
+            Err(ParseError::new(Span::new(0, 1), "expected an integer"))
+        );
+        assert_eq!(
+            integer::<i8>().parse("-300").unwrap_err(),
+            ParseError::new(Span::new(0, 4), "integer out of range")
+        );
+        assert_eq!(
+            integer::<u32>().parse("4 2").unwrap_err(),
+            ParseError::new(Span::new(2, 3), "expected the end of the input")
+        );
+    }
+    #[test]
+    fn test_separated() {
+        let list = integer::<u32>().trim().sep_by(literal(","));
+        assert_eq!(list.parse("1, 2 ,3"), Ok(vec![1, 2, 3]));
+        assert_eq!(list.parse("1,,3").unwrap_err().span, Span::new(2, 3));
+        let pair = integer::<u32>().skip(literal("-")).and(integer::<u32>());
+        assert_eq!(pair.parse("165432-707912"), Ok((165432, 707912)));
+    }
+    #[test]
+    fn test_keyed() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Turn {
+            Left,
+            Right,
+            Around,
+        }
+        let turn = keyed(vec![
+            ("L", Turn::Left),
+            ("R", Turn::Right),
+            ("RR", Turn::Around),
+        ]);
+        assert_eq!(
+            turn.sep_by(literal(" ")).parse("L RR R"),
+            Ok(vec![Turn::Left, Turn::Around, Turn::Right])
+        );
+        let error = keyed(vec![("L", Turn::Left)]).parse("X").unwrap_err();
+        assert_eq!(error.message, "expected one of \"L\"");
+    }
+    #[test]
+    fn test_lines() {
+        let rows = integer::<u32>().trim().sep_by(literal(",")).lines();
+        assert_eq!(rows.parse("1,2\n\n3\n"), Ok(vec![vec![1, 2], vec![3]]));
+        let error = rows.parse("1,2\n3,x\n").unwrap_err();
+        assert_eq!(error.span, Span::new(6, 7));
+        assert_eq!(error.line_column("1,2\n3,x\n"), (2, 3));
+    }
+    #[test]
+    fn test_blocks() {
+        let elves = integer::<u32>().lines().blocks();
+        assert_eq!(
+            elves.parse("\n1\n2\n\n  \n3\n"),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+        assert_eq!(elves.parse(""), Ok(vec![]));
+        let error = elves.parse("1\n2\n\n3\nx\n").unwrap_err();
+        assert_eq!(error.line_column("1\n2\n\n3\nx\n"), (5, 1));
+        // A block's parser has to use all of it
+        let error = integer::<u32>().blocks().parse("1\n2\n").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(Span::new(2, 3), "expected the end of the block")
+        );
+    }
+    #[test]
+    fn test_grid() {
+        let cells = grid(|c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        });
+        let parsed = cells.parse("#.\n.#\n").unwrap();
+        assert_eq!(parsed.width(), 2);
+        assert_eq!(parsed.row(1), &[false, true]);
+        assert_eq!(cells.parse("#.\n.?\n").unwrap_err().span, Span::new(4, 5));
+        assert_eq!(cells.parse("#.\n.\n").unwrap_err().span, Span::new(3, 4));
+    }
+}
+
+use crate::grid::Grid;
+use std::fmt;
+use std::str::FromStr;
+
+/// A range of byte offsets into the input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Span {
+        Span { start, end }
+    }
+}
+
+/// Text that a parser didn't expect.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub span: Span,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new<S: Into<String>>(span: Span, message: S) -> ParseError {
+        ParseError {
+            span,
+            message: message.into(),
+        }
+    }
+    /// The 1-based line and column of the start of the error in `source`
+    pub fn line_column(&self, source: &str) -> (usize, usize) {
+        let before = &source[..self.span.start.min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+        (line, column)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {}..{}",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The part of the source a parser hasn't consumed yet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Input<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(source: &'a str) -> Input<'a> {
+        Input {
+            source,
+            position: 0,
+        }
+    }
+    /// The byte offset of the input into the source
+    pub fn position(&self) -> usize {
+        self.position
+    }
+    /// The text that hasn't been consumed
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+    /// Consume `bytes` bytes
+    pub fn advance(&self, bytes: usize) -> Input<'a> {
+        Input {
+            source: self.source,
+            position: self.position + bytes,
+        }
+    }
+    /// Consume characters while `predicate` holds, returning them
+    pub fn take_while<F: Fn(char) -> bool>(&self, predicate: F) -> (&'a str, Input<'a>) {
+        let rest = self.rest();
+        let length = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        (&rest[..length], self.advance(length))
+    }
+    /// Only the next `bytes` bytes, as if the source ended there
+    fn truncate(&self, bytes: usize) -> Input<'a> {
+        Input {
+            source: &self.source[..self.position + bytes],
+            position: self.position,
+        }
+    }
+    /// An error spanning the next character, or the end of the input
+    pub fn error<S: Into<String>>(&self, message: S) -> ParseError {
+        let length = self.rest().chars().next().map_or(0, char::len_utf8);
+        ParseError::new(Span::new(self.position, self.position + length), message)
+    }
+}
+
+type Run<'p, T> = dyn for<'a> Fn(Input<'a>) -> Result<(T, Input<'a>), ParseError> + 'p;
+
+/// Something that parses a `T` from the start of an input.
+pub struct Parser<'p, T> {
+    run: Box<Run<'p, T>>,
+}
+
+impl<'p, T: 'p> Parser<'p, T> {
+    pub fn new<F>(run: F) -> Parser<'p, T>
+    where
+        F: for<'a> Fn(Input<'a>) -> Result<(T, Input<'a>), ParseError> + 'p,
+    {
+        Parser { run: Box::new(run) }
+    }
+    /// Parse a `T` from the start of `input`, returning what's left
+    pub fn parse_at<'a>(&self, input: Input<'a>) -> Result<(T, Input<'a>), ParseError> {
+        (self.run)(input)
+    }
+    /// Parse all of `source`, allowing trailing whitespace
+    pub fn parse(&self, source: &str) -> Result<T, ParseError> {
+        let (value, rest) = self.parse_at(Input::new(source))?;
+        let (_, rest) = rest.take_while(char::is_whitespace);
+        if rest.is_empty() {
+            Ok(value)
+        } else {
+            Err(rest.error("expected the end of the input"))
+        }
+    }
+    pub fn map<U: 'p, F: Fn(T) -> U + 'p>(self, f: F) -> Parser<'p, U> {
+        Parser::new(move |input| {
+            let (value, rest) = self.parse_at(input)?;
+            Ok((f(value), rest))
+        })
+    }
+    /// Convert the value, failing with an error spanning everything this parser
+    /// consumed if `f` fails
+    pub fn try_map<U: 'p, F: Fn(T) -> Result<U, String> + 'p>(self, f: F) -> Parser<'p, U> {
+        Parser::new(move |input: Input| {
+            let (value, rest) = self.parse_at(input)?;
+            match f(value) {
+                Ok(value) => Ok((value, rest)),
+                Err(message) => Err(ParseError::new(
+                    Span::new(input.position(), rest.position()),
+                    message,
+                )),
+            }
+        })
+    }
+    /// Parse this and then `other`, keeping both
+    pub fn and<U: 'p>(self, other: Parser<'p, U>) -> Parser<'p, (T, U)> {
+        Parser::new(move |input| {
+            let (first, rest) = self.parse_at(input)?;
+            let (second, rest) = other.parse_at(rest)?;
+            Ok(((first, second), rest))
+        })
+    }
+    /// Parse this and then `other`, keeping only this
+    pub fn skip<U: 'p>(self, other: Parser<'p, U>) -> Parser<'p, T> {
+        self.and(other).map(|(first, _)| first)
+    }
+    /// Skip spaces and tabs before and after this
+    pub fn trim(self) -> Parser<'p, T> {
+        Parser::new(move |input: Input| {
+            let (_, rest) = input.take_while(is_blank);
+            let (value, rest) = self.parse_at(rest)?;
+            let (_, rest) = rest.take_while(is_blank);
+            Ok((value, rest))
+        })
+    }
+    /// One or more of this separated by `separator`
+    pub fn sep_by<S: 'p>(self, separator: Parser<'p, S>) -> Parser<'p, Vec<T>> {
+        Parser::new(move |input| {
+            let (first, mut rest) = self.parse_at(input)?;
+            let mut values = vec![first];
+            while let Ok((_, after)) = separator.parse_at(rest) {
+                let (value, after) = self.parse_at(after)?;
+                values.push(value);
+                rest = after;
+            }
+            Ok((values, rest))
+        })
+    }
+    /// This on each non-empty line, until the end of the input
+    pub fn lines(self) -> Parser<'p, Vec<T>> {
+        Parser::new(move |input: Input| {
+            let mut values = vec![];
+            let mut rest = input;
+            loop {
+                let (_, after) = rest.take_while(|c| c == '\n' || c == '\r');
+                rest = after;
+                if rest.is_empty() {
+                    return Ok((values, rest));
+                }
+                let (value, after) = self.parse_at(rest)?;
+                if !(after.is_empty() || after.rest().starts_with(&['\n', '\r'][..])) {
+                    return Err(after.error("expected the end of the line"));
+                }
+                values.push(value);
+                rest = after;
+            }
+        })
+    }
+    /// This on each block of lines, where blocks are separated by blank
+    /// lines, until the end of the input
+    pub fn blocks(self) -> Parser<'p, Vec<T>> {
+        Parser::new(move |input: Input| {
+            let blank = |line: &str| line.trim().is_empty();
+            let mut values = vec![];
+            let mut rest = input;
+            loop {
+                let skipped = rest
+                    .rest()
+                    .split_inclusive('\n')
+                    .take_while(|line| blank(line))
+                    .map(str::len)
+                    .sum();
+                rest = rest.advance(skipped);
+                if rest.is_empty() {
+                    return Ok((values, rest));
+                }
+                let length = rest
+                    .rest()
+                    .split_inclusive('\n')
+                    .take_while(|line| !blank(line))
+                    .map(str::len)
+                    .sum();
+                let (value, after) = self.parse_at(rest.truncate(length))?;
+                let (_, after) = after.take_while(char::is_whitespace);
+                if !after.is_empty() {
+                    return Err(after.error("expected the end of the block"));
+                }
+                values.push(value);
+                rest = rest.advance(length);
+            }
+        })
+    }
+}
+
+fn is_blank(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+/// An integer, optionally negative if `T` can be
+pub fn integer<'p, T: FromStr + 'p>() -> Parser<'p, T> {
+    // Unsigned types don't read "-0", so a minus sign isn't part of their numbers
+    let signed = T::from_str("-0").is_ok();
+    Parser::new(move |input: Input| {
+        let sign = if signed && input.rest().starts_with('-') {
+            1
+        } else {
+            0
+        };
+        let (digits, _) = input.advance(sign).take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(input.error("expected an integer"));
+        }
+        let length = sign + digits.len();
+        match T::from_str(&input.rest()[..length]) {
+            Ok(value) => Ok((value, input.advance(length))),
+            Err(_) => Err(ParseError::new(
+                Span::new(input.position(), input.position() + length),
+                "integer out of range",
+            )),
+        }
+    })
+}
+
+/// Exactly `text`
+pub fn literal<'p>(text: &'p str) -> Parser<'p, ()> {
+    Parser::new(move |input: Input| {
+        if input.rest().starts_with(text) {
+            Ok(((), input.advance(text.len())))
+        } else {
+            Err(input.error(format!("expected {:?}", text)))
+        }
+    })
+}
+
+/// The value for the longest key that the input starts with, e.g. to parse the
+/// variant of an enum from its prefix
+pub fn keyed<'p, V: Clone + 'p>(table: Vec<(&'p str, V)>) -> Parser<'p, V> {
+    Parser::new(move |input: Input| {
+        table
+            .iter()
+            .filter(|(key, _)| input.rest().starts_with(key))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(key, value)| (value.clone(), input.advance(key.len())))
+            .ok_or_else(|| {
+                let keys: Vec<String> = table.iter().map(|(key, _)| format!("{:?}", key)).collect();
+                input.error(format!("expected one of {}", keys.join(", ")))
+            })
+    })
+}
+
+/// A rectangular grid of characters, converting each character with `cell`,
+/// which returns `None` for characters that aren't allowed
+pub fn grid<'p, T: 'p, F: Fn(char) -> Option<T> + 'p>(cell: F) -> Parser<'p, Grid<T>> {
+    Parser::new(move |input: Input| {
+        let row = Parser::new(|input: Input| {
+            let (line, rest) = input.take_while(|c| c != '\n' && c != '\r');
+            let mut cells = vec![];
+            for (i, c) in line.char_indices() {
+                match cell(c) {
+                    Some(value) => cells.push(value),
+                    None => return Err(input.advance(i).error(format!("unexpected {:?}", c))),
+                }
+            }
+            Ok(((cells, input.position()), rest))
+        });
+        let (rows, rest) = row.lines().parse_at(input)?;
+        let width = rows.first().map_or(0, |(cells, _)| cells.len());
+        if let Some((cells, start)) = rows.iter().find(|(cells, _)| cells.len() != width) {
+            return Err(ParseError::new(
+                Span::new(*start, *start + 1),
+                format!("expected {} cells, not {}", width, cells.len()),
+            ));
+        }
+        match Grid::from_rows(rows.into_iter().map(|(cells, _)| cells).collect()) {
+            Ok(grid) => Ok((grid, rest)),
+            Err(error) => Err(input.error(error.to_string())),
+        }
+    })
+}
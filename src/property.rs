@@ -0,0 +1,443 @@
+// Property tests: checking that something holds for lots of random inputs, and
+// shrinking any input it doesn't hold for down to a small one
+//
+// A property is a function that returns `Err` or panics when it doesn't hold.
+// `Config::from_env` reads `PROPERTY_CASES` and `PROPERTY_SEED`, to run many
+// more cases fuzzing style, or to reproduce a failure from its seed.
+
+#[cfg(test)]
+mod tests {
+    use crate::property::*;
+    fn config() -> Config {
+        Config {
+            cases: 500,
+            ..Config::default()
+        }
+    }
+    #[test]
+    fn test_passing() {
+        let lengths = check(&config(), &text("ab", 20), |s: &String| {
+            if s.len() <= 20 {
+                Ok(())
+            } else {
+                Err(format!("{} is too long", s.len()))
+            }
+        });
+        assert!(lengths.is_ok());
+    }
+    #[test]
+    fn test_shrink_text() {
+        // Any string with an `x` fails, and the smallest is just `x`
+        let failure = check(&config(), &text("abx", 30), |s: &String| {
+            if s.contains('x') {
+                Err("found x".to_string())
+            } else {
+                Ok(())
+            }
+        })
+        .unwrap_err();
+        assert_eq!(failure.shrunk, "x");
+        assert_eq!(failure.message, "found x");
+        assert!(failure.original.contains('x'));
+    }
+    #[test]
+    fn test_shrink_integers() {
+        let failure = check(&config(), &integers(0, 1 << 40), |&n: &u64| {
+            assert!(n < 1000, "too big");
+            Ok(())
+        })
+        .unwrap_err();
+        assert_eq!(failure.shrunk, 1000);
+        assert!(failure.message.contains("too big"));
+    }
+    #[test]
+    fn test_shrink_vec() {
+        // A list with a sum of at least 10, shrunk until any smaller one sums
+        // to less
+        let failure = check(&config(), &vec_of(integers(0, 9), 8), |v: &Vec<u64>| {
+            if v.iter().sum::<u64>() < 10 {
+                Ok(())
+            } else {
+                Err("sum too big".to_string())
+            }
+        })
+        .unwrap_err();
+        assert_eq!(failure.shrunk.iter().sum::<u64>(), 10);
+        assert!(failure.shrunk.iter().all(|&n| n > 0));
+    }
+    #[test]
+    fn test_mutations() {
+        let mut rng = XorShift::new(1);
+        let strategy = mutations(&["R8,U5"], "RUDL0123456789,");
+        let mutated: Vec<String> = (0..100).map(|_| strategy.generate(&mut rng)).collect();
+        assert!(mutated.iter().any(|s| s != "R8,U5"));
+        assert!(mutated
+            .iter()
+            .all(|s| s.chars().all(|c| "RUDL0123456789,".contains(c))));
+    }
+    #[test]
+    fn test_reproducible() {
+        let fails = |config: &Config| {
+            check(config, &integers(0, u64::MAX), |&n: &u64| match n % 7 {
+                0 => Err("multiple of 7".to_string()),
+                _ => Ok(()),
+            })
+            .unwrap_err()
+        };
+        let a = fails(&config());
+        let b = fails(&config());
+        assert_eq!((a.case, a.original), (b.case, b.original));
+        assert_eq!(a.shrunk, 0);
+    }
+}
+
+use crate::rng::XorShift;
+use std::cell::Cell;
+use std::env;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+/// How to make random values, and smaller versions of a value.
+pub trait Strategy {
+    type Value: Clone + fmt::Debug;
+    fn generate(&self, rng: &mut XorShift) -> Self::Value;
+    /// Simpler values to try in place of one that failed, most promising
+    /// first
+    fn shrink(&self, _value: &Self::Value) -> Vec<Self::Value> {
+        vec![]
+    }
+}
+
+/// Strings of up to `max_len` characters from `alphabet`, see `text`.
+#[derive(Clone, Debug)]
+pub struct Text {
+    alphabet: Vec<char>,
+    max_len: usize,
+}
+
+/// Strings of up to `max_len` characters from `alphabet`. They shrink
+/// towards shorter strings of the first character in `alphabet`.
+pub fn text(alphabet: &str, max_len: usize) -> Text {
+    Text {
+        alphabet: alphabet.chars().collect(),
+        max_len,
+    }
+}
+
+/// A random character from `alphabet`.
+fn pick(rng: &mut XorShift, alphabet: &[char]) -> char {
+    *rng.choose(alphabet)
+        .expect("an alphabet with characters in it")
+}
+
+/// Shorter versions of `chars`, then versions with characters swapped for
+/// `simplest`.
+fn shrink_chars(chars: &[char], simplest: char) -> Vec<String> {
+    let mut shrunk = vec![];
+    let len = chars.len();
+    // Take out big chunks first, then smaller ones
+    let mut size = len;
+    while size > 0 {
+        for start in (0..len).step_by(size) {
+            let mut shorter = chars[..start].to_vec();
+            shorter.extend(&chars[std::cmp::min(start + size, len)..]);
+            shrunk.push(shorter.into_iter().collect());
+        }
+        size /= 2;
+    }
+    for (i, &c) in chars.iter().enumerate() {
+        if c != simplest {
+            let mut simpler = chars.to_vec();
+            simpler[i] = simplest;
+            shrunk.push(simpler.into_iter().collect());
+        }
+    }
+    shrunk
+}
+
+impl Strategy for Text {
+    type Value = String;
+    fn generate(&self, rng: &mut XorShift) -> String {
+        let len = rng.range(0, self.max_len as u64);
+        (0..len).map(|_| pick(rng, &self.alphabet)).collect()
+    }
+    fn shrink(&self, value: &String) -> Vec<String> {
+        let chars: Vec<char> = value.chars().collect();
+        shrink_chars(&chars, self.alphabet[0])
+    }
+}
+
+/// Valid inputs with a few random edits, see `mutations`.
+#[derive(Clone, Debug)]
+pub struct Mutations {
+    seeds: Vec<String>,
+    alphabet: Vec<char>,
+}
+
+/// One of `seeds`, usually valid inputs, with one to four random edits:
+/// characters from `alphabet` inserted or swapped in, characters taken out,
+/// or a slice repeated or cut off. Gets at the edge cases just past valid
+/// input that purely random text rarely reaches.
+pub fn mutations(seeds: &[&str], alphabet: &str) -> Mutations {
+    Mutations {
+        seeds: seeds.iter().map(|seed| seed.to_string()).collect(),
+        alphabet: alphabet.chars().collect(),
+    }
+}
+
+impl Strategy for Mutations {
+    type Value = String;
+    fn generate(&self, rng: &mut XorShift) -> String {
+        let seed = rng.choose(&self.seeds).expect("at least one seed");
+        let mut chars: Vec<char> = seed.chars().collect();
+        for _ in 0..rng.range(1, 4) {
+            let at = rng.range(0, chars.len() as u64) as usize;
+            match rng.below(5) {
+                0 => chars.insert(at, pick(rng, &self.alphabet)),
+                1 if at < chars.len() => {
+                    chars.remove(at);
+                }
+                2 if at < chars.len() => chars[at] = pick(rng, &self.alphabet),
+                3 => {
+                    let end = rng.range(at as u64, chars.len() as u64) as usize;
+                    let slice = chars[at..end].to_vec();
+                    chars.splice(at..at, slice);
+                }
+                _ => chars.truncate(at),
+            }
+        }
+        chars.into_iter().collect()
+    }
+    fn shrink(&self, value: &String) -> Vec<String> {
+        let chars: Vec<char> = value.chars().collect();
+        shrink_chars(&chars, self.alphabet[0])
+    }
+}
+
+/// Numbers in an inclusive range, see `integers`.
+#[derive(Clone, Copy, Debug)]
+pub struct Integers {
+    low: u64,
+    high: u64,
+}
+
+/// Numbers in `low..=high`, now and then right at the ends, shrinking
+/// towards `low`.
+pub fn integers(low: u64, high: u64) -> Integers {
+    Integers { low, high }
+}
+
+impl Strategy for Integers {
+    type Value = u64;
+    fn generate(&self, rng: &mut XorShift) -> u64 {
+        if rng.chance(1, 8) {
+            *rng.choose(&[self.low, self.high]).unwrap()
+        } else {
+            rng.range(self.low, self.high)
+        }
+    }
+    fn shrink(&self, &value: &u64) -> Vec<u64> {
+        let mut shrunk = vec![];
+        // Halve the distance to `low`, then creep closer
+        let mut distance = value - self.low;
+        while distance > 0 {
+            shrunk.push(value - distance);
+            distance /= 2;
+        }
+        shrunk
+    }
+}
+
+/// Lists of values from another strategy, see `vec_of`.
+#[derive(Clone, Copy, Debug)]
+pub struct VecOf<S> {
+    element: S,
+    max_len: usize,
+}
+
+/// Lists of up to `max_len` values from `element`, shrinking by taking
+/// values out and then by shrinking each value.
+pub fn vec_of<S: Strategy>(element: S, max_len: usize) -> VecOf<S> {
+    VecOf { element, max_len }
+}
+
+impl<S: Strategy> Strategy for VecOf<S> {
+    type Value = Vec<S::Value>;
+    fn generate(&self, rng: &mut XorShift) -> Vec<S::Value> {
+        let len = rng.range(0, self.max_len as u64);
+        (0..len).map(|_| self.element.generate(rng)).collect()
+    }
+    fn shrink(&self, value: &Vec<S::Value>) -> Vec<Vec<S::Value>> {
+        let mut shrunk = vec![];
+        let len = value.len();
+        let mut size = len;
+        while size > 0 {
+            for start in (0..len).step_by(size) {
+                let mut shorter = value[..start].to_vec();
+                shorter.extend_from_slice(&value[std::cmp::min(start + size, len)..]);
+                shrunk.push(shorter);
+            }
+            size /= 2;
+        }
+        for (i, element) in value.iter().enumerate() {
+            for simpler in self.element.shrink(element) {
+                let mut smaller = value.clone();
+                smaller[i] = simpler;
+                shrunk.push(smaller);
+            }
+        }
+        shrunk
+    }
+}
+
+impl<A: Strategy, B: Strategy> Strategy for (A, B) {
+    type Value = (A::Value, B::Value);
+    fn generate(&self, rng: &mut XorShift) -> Self::Value {
+        (self.0.generate(rng), self.1.generate(rng))
+    }
+    fn shrink(&self, (a, b): &Self::Value) -> Vec<Self::Value> {
+        let firsts = self.0.shrink(a).into_iter().map(|a| (a, b.clone()));
+        let seconds = self.1.shrink(b).into_iter().map(|b| (a.clone(), b));
+        firsts.chain(seconds).collect()
+    }
+}
+
+/// How many cases to try, and where to start.
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    pub cases: usize,
+    pub seed: u64,
+    /// The most smaller failing values to move to while shrinking
+    pub max_shrinks: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            cases: 256,
+            seed: 2019,
+            max_shrinks: 1000,
+        }
+    }
+}
+
+impl Config {
+    /// The default, with `cases` and `seed` from the `PROPERTY_CASES` and
+    /// `PROPERTY_SEED` environment variables if they're set
+    pub fn from_env() -> Config {
+        let default = Config::default();
+        let var = |name: &str| env::var(name).ok().and_then(|value| value.parse().ok());
+        Config {
+            cases: var("PROPERTY_CASES").map_or(default.cases, |cases| cases as usize),
+            seed: var("PROPERTY_SEED").unwrap_or(default.seed),
+            ..default
+        }
+    }
+}
+
+/// A value a property didn't hold for, and the smallest one it was shrunk to.
+#[derive(Clone, Debug)]
+pub struct Failure<V> {
+    pub seed: u64,
+    /// Which case found it, counting from 0
+    pub case: usize,
+    pub original: V,
+    pub shrunk: V,
+    pub shrinks: usize,
+    /// Why the property didn't hold for `shrunk`
+    pub message: String,
+}
+
+impl<V: fmt::Debug> fmt::Display for Failure<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "failed for {:?}: {}", self.shrunk, self.message)?;
+        write!(
+            f,
+            "shrunk {} times from {:?}, case {} of PROPERTY_SEED={}",
+            self.shrinks, self.original, self.case, self.seed
+        )
+    }
+}
+
+thread_local! {
+    /// Whether panics on this thread are expected, and shouldn't be printed
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Checks `property` for `value`, turning a panic into an `Err`.
+fn holds<V, F: Fn(&V) -> Result<(), String>>(property: &F, value: &V) -> Result<(), String> {
+    // Keep the default hook for every other panic
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+    QUIET.with(|quiet| quiet.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| property(value)));
+    QUIET.with(|quiet| quiet.set(false));
+    result.unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "a panic".to_string());
+        Err(format!("panicked: {}", message))
+    })
+}
+
+/// Checks `property` for `config.cases` values from `strategy`, shrinking
+/// the first one it doesn't hold for.
+pub fn check<S, F>(config: &Config, strategy: &S, property: F) -> Result<(), Failure<S::Value>>
+where
+    S: Strategy,
+    F: Fn(&S::Value) -> Result<(), String>,
+{
+    let mut rng = XorShift::new(config.seed);
+    for case in 0..config.cases {
+        let original = strategy.generate(&mut rng);
+        let mut message = match holds(&property, &original) {
+            Ok(()) => continue,
+            Err(message) => message,
+        };
+        // Keep moving to the first smaller value that still fails
+        let mut shrunk = original.clone();
+        let mut shrinks = 0;
+        'shrinking: while shrinks < config.max_shrinks {
+            for smaller in strategy.shrink(&shrunk) {
+                if let Err(smaller_message) = holds(&property, &smaller) {
+                    shrunk = smaller;
+                    message = smaller_message;
+                    shrinks += 1;
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+        return Err(Failure {
+            seed: config.seed,
+            case,
+            original,
+            shrunk,
+            shrinks,
+            message,
+        });
+    }
+    Ok(())
+}
+
+/// Checks `property` like `check`, with the config from the environment,
+/// and panics with the shrunk failure if it doesn't hold.
+pub fn assert_property<S, F>(name: &str, strategy: &S, property: F)
+where
+    S: Strategy,
+    F: Fn(&S::Value) -> Result<(), String>,
+{
+    if let Err(failure) = check(&Config::from_env(), strategy, property) {
+        panic!("property {:?} {}", name, failure);
+    }
+}
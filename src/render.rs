@@ -0,0 +1,222 @@
+// Render traced wires as SVG or ASCII art so we can actually see them
+
+#[cfg(test)]
+mod tests {
+    use crate::render::*;
+    use crate::y2019::day03::*;
+    #[test]
+    fn test_bounds() {
+        let red = trace_wire(string_to_path("R8,U5,L5,D3"));
+        let green = trace_wire(string_to_path("U7,R6,D4,L4"));
+        let bounds = bounds(&[red, green]);
+        assert_eq!(
+            (bounds.min(), bounds.max()),
+            (Point::origin(), Point::new(8, 7))
+        );
+        assert_eq!(bounds.width(), 9);
+        assert_eq!(bounds.height(), 8);
+    }
+    #[test]
+    fn test_svg() {
+        let red = trace_wire(string_to_path("R8,U5,L5,D3"));
+        let green = trace_wire(string_to_path("U7,R6,D4,L4"));
+        let svg = svg(&[red, green]);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        // Two intersections plus the origin
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert!(svg.contains("points=\"0,0 8,0 8,-5 3,-5 3,-2\""));
+    }
+    #[test]
+    fn test_ascii() {
+        let red = trace_wire(string_to_path("R8,U5,L5,D3"));
+        let green = trace_wire(string_to_path("U7,R6,D4,L4"));
+        assert_eq!(
+            ascii(&[red, green], 80, 40),
+            "\
+2222222..
+2.....2..
+2..111X11
+2..1..2.1
+2.2X222.1
+2..1....1
+2.......1
+o11111111
+"
+        );
+    }
+    #[test]
+    fn test_ascii_diagonal() {
+        let red = trace_wire(string_to_path("UR2,R1"));
+        let green = trace_wire(string_to_path("U2"));
+        assert_eq!(ascii(&[red, green], 80, 40), "2.11\n21..\no...\n");
+    }
+    #[test]
+    fn test_ascii_scaled() {
+        let red = trace_wire(string_to_path("R8,U5,L5,D3"));
+        let green = trace_wire(string_to_path("U7,R6,D4,L4"));
+        let art = ascii(&[red, green], 5, 5);
+        // Scaled down by 2, so 9x8 becomes 5x4
+        assert_eq!(art, "2222.\n211X1\n2X221\no1111\n");
+    }
+}
+
+use crate::geometry::BoundingBox;
+use crate::y2019::day03::{self, Line, Point};
+use std::fmt::Write;
+
+/// Stroke colors for each wire, cycled if there are more wires than colors.
+const COLORS: [&str; 6] = [
+    "#d62728", "#2ca02c", "#1f77b4", "#ff7f0e", "#9467bd", "#8c564b",
+];
+
+/// The bounding box of all the wires, always including the origin.
+pub fn bounds(wires: &[Vec<Line>]) -> BoundingBox {
+    let mut bounds = BoundingBox::new(Point::origin());
+    for line in wires.iter().flatten() {
+        bounds.include(line.p1());
+        bounds.include(line.p2());
+    }
+    bounds
+}
+
+/// All the points where any two different wires cross, excluding the origin.
+fn all_intersections(wires: &[Vec<Line>]) -> Vec<Point> {
+    let mut intersections = vec![];
+    for (i, red) in wires.iter().enumerate() {
+        for green in &wires[i + 1..] {
+            intersections.extend(day03::wire_intersections(red, green));
+        }
+    }
+    intersections
+}
+
+/// Render the wires as an SVG document with one colored polyline per wire, a
+/// circle on each intersection, and a black circle on the origin.
+///
+/// SVG's y axis points down, so y coordinates are negated to keep "up" up.
+pub fn svg(wires: &[Vec<Line>]) -> String {
+    let bounds = bounds(wires);
+    let (width, height) = (bounds.width() as usize, bounds.height() as usize);
+    // Pad the view box so the markers at the edges aren't cut off
+    let size = std::cmp::max(width, height) as f64;
+    let pad = size / 20.0;
+    let radius = size / 100.0;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+        bounds.min().x() as f64 - pad,
+        -bounds.max().y() as f64 - pad,
+        (width - 1) as f64 + 2.0 * pad,
+        (height - 1) as f64 + 2.0 * pad,
+    )
+    .unwrap();
+    for (i, wire) in wires.iter().enumerate() {
+        let mut points = vec![];
+        if let Some(first) = wire.first() {
+            points.push(first.p1());
+        }
+        points.extend(wire.iter().map(|line| line.p2()));
+        let points: Vec<String> = points
+            .iter()
+            .map(|point| format!("{},{}", point.x(), -point.y()))
+            .collect();
+        writeln!(
+            svg,
+            r#"  <polyline points="{}" fill="none" stroke="{}" stroke-width="2" vector-effect="non-scaling-stroke"/>"#,
+            points.join(" "),
+            COLORS[i % COLORS.len()],
+        )
+        .unwrap();
+    }
+    for point in all_intersections(wires) {
+        writeln!(
+            svg,
+            r#"  <circle cx="{}" cy="{}" r="{}" fill="none" stroke="black" vector-effect="non-scaling-stroke"/>"#,
+            point.x(),
+            -point.y(),
+            radius,
+        )
+        .unwrap();
+    }
+    writeln!(
+        svg,
+        r#"  <circle cx="0" cy="0" r="{}" fill="black"/>"#,
+        radius
+    )
+    .unwrap();
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Render the wires as ASCII art no larger than `max_width` by `max_height`
+/// characters, scaling the wires down (uniformly) if necessary.
+///
+/// Each wire is drawn with its 1-based index (mod 10), cells crossed by more
+/// than one wire are drawn as `X`, and the origin is drawn as `o`. If the wires
+/// are scaled down, a cell is drawn if any point it covers is drawn.
+pub fn ascii(wires: &[Vec<Line>], max_width: usize, max_height: usize) -> String {
+    let bounds = bounds(wires);
+    let (width, height) = (bounds.width() as usize, bounds.height() as usize);
+    let scale = std::cmp::max(
+        width.div_ceil(max_width.max(1)),
+        height.div_ceil(max_height.max(1)),
+    );
+    let columns = width.div_ceil(scale);
+    let rows = height.div_ceil(scale);
+    // The cell a point is in, with row 0 at the top (the largest y)
+    let cell = |x: isize, y: isize| {
+        (
+            (x - bounds.min().x()) as usize / scale,
+            (bounds.max().y() - y) as usize / scale,
+        )
+    };
+
+    let mut canvas = vec![vec!['.'; columns]; rows];
+    for (i, wire) in wires.iter().enumerate() {
+        let symbol = std::char::from_digit(((i + 1) % 10) as u32, 10).unwrap();
+        // Cells this wire has drawn, so a wire crossing itself isn't an `X`
+        let mut drawn = vec![vec![false; columns]; rows];
+        let mut draw = |(column, row): (usize, usize)| {
+            if !drawn[row][column] {
+                drawn[row][column] = true;
+                canvas[row][column] = match canvas[row][column] {
+                    '.' => symbol,
+                    _ => 'X',
+                };
+            }
+        };
+        for line in wire {
+            if line.is_diagonal() {
+                // Diagonals don't fill their bounding box, so draw each point
+                let step = line.p2() - line.p1();
+                let step = Point::new(step.x().signum(), step.y().signum());
+                let mut point = line.p1();
+                draw(cell(point.x(), point.y()));
+                while point != line.p2() {
+                    point += step;
+                    draw(cell(point.x(), point.y()));
+                }
+                continue;
+            }
+            let (left, top) = cell(line.x_min(), line.y_max());
+            let (right, bottom) = cell(line.x_max(), line.y_min());
+            for row in top..=bottom {
+                for column in left..=right {
+                    draw((column, row));
+                }
+            }
+        }
+    }
+    let (x, y) = cell(0, 0);
+    canvas[y][x] = 'o';
+
+    let mut art = String::new();
+    for row in canvas {
+        art.extend(row);
+        art.push('\n');
+    }
+    art
+}
@@ -0,0 +1,73 @@
+// A small deterministic random number generator for randomized tests
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::*;
+    #[test]
+    fn test_deterministic() {
+        let mut a = XorShift::new(42);
+        let mut b = XorShift::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+    #[test]
+    fn test_below() {
+        let mut rng = XorShift::new(0);
+        let mut seen = [false; 10];
+        for _ in 0..1000 {
+            let n = rng.below(10);
+            assert!(n < 10);
+            seen[n as usize] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+        assert!((0..1000).all(|_| (3..=5).contains(&rng.range(3, 5))));
+    }
+}
+
+/// Marsaglia's xorshift64*, which is plenty random for generating test cases.
+#[derive(Clone, Debug)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// A generator seeded with `seed`. The same seed always gives the same
+    /// numbers.
+    pub fn new(seed: u64) -> XorShift {
+        // The state must never be zero, so mix the seed with a constant
+        XorShift {
+            state: seed ^ 0x9E37_79B9_7F4A_7C15 | 1,
+        }
+    }
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+    /// A number in `0..bound`. Panics if `bound` is zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound must be positive");
+        self.next_u64() % bound
+    }
+    /// A number in `low..=high`
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        match (high - low).checked_add(1) {
+            Some(bound) => low + self.below(bound),
+            None => self.next_u64(),
+        }
+    }
+    /// True with probability `numerator / denominator`
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+    /// A random element of `items`, or `None` if it's empty
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            Some(&items[self.below(items.len() as u64) as usize])
+        }
+    }
+}
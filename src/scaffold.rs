@@ -0,0 +1,322 @@
+// Generating the files for a new day, so starting one is a single command
+//
+// A new day gets a module at `src/y<year>/day<NN>.rs` with a `Solution`
+// skeleton, a fixture stub, and an empty input file. It's registered in front
+// of the `// new-day:` marker comments in the year's `mod.rs`, and a new year
+// is registered the same way in `src/lib.rs` and `src/solution.rs`.
+
+#[cfg(test)]
+mod tests {
+    use crate::scaffold::*;
+    use std::env;
+    fn scratch(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}-{}", name, std::process::id()));
+        fs::remove_dir_all(&root).ok();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/lib.rs"),
+            "pub mod solution;\n// new-day: years\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/solution.rs"),
+            "const YEARS: &[(u32, Registry)] = &[\n    // new-day: years\n];\n",
+        )
+        .unwrap();
+        root
+    }
+    #[test]
+    fn test_register() {
+        let source = "a\n    // new-day: here\nb\n";
+        let registered = register(source, "here", "c").unwrap();
+        assert_eq!(registered, "a\n    c\n    // new-day: here\nb\n");
+        // Registering again changes nothing
+        assert_eq!(register(&registered, "here", "c").unwrap(), registered);
+        assert!(register(source, "there", "c").is_err());
+        // In sorted order with the lines above the marker
+        let source = "mod x;\n\nmod a;\nmod c;\n// new-day: here\n";
+        assert_eq!(
+            register(source, "here", "mod b;").unwrap(),
+            "mod x;\n\nmod a;\nmod b;\nmod c;\n// new-day: here\n"
+        );
+    }
+    #[test]
+    fn test_paths() {
+        let new = NewDay::new(2015, 7).unwrap();
+        assert_eq!(new.module(), "day07");
+        assert_eq!(new.name(), "Day7");
+        assert_eq!(new.source(), Path::new("src/y2015/day07.rs"));
+        assert_eq!(new.input(), "input/2015/day07");
+        assert_eq!(new.fixture(), Path::new("fixtures/2015/day07/example.txt"));
+        assert!(NewDay::new(2015, 26).is_err());
+        assert!(NewDay::new(2014, 1).is_err());
+    }
+    #[test]
+    fn test_generate() {
+        let root = scratch("generate");
+        let new = NewDay::new(2015, 1).unwrap();
+        let created = new.generate(&root).unwrap();
+        assert_eq!(created.len(), 4);
+        assert!(created.iter().all(|path| path.exists()));
+        let read = |path: &str| fs::read_to_string(root.join(path)).unwrap();
+        assert!(read("src/lib.rs").contains("pub mod y2015;\n// new-day: years"));
+        assert!(read("src/y2015/mod.rs").contains("pub mod day01;\n// new-day: days"));
+        assert!(read("src/solution.rs").contains("    (2015, crate::y2015::registry),\n"));
+        assert!(read("src/y2015/mod.rs")
+            .contains("        Day::of::<day01::Day1>(2015, 1, \"input/2015/day01\"),\n"));
+        assert!(read("src/y2015/day01.rs").contains("impl Solution for Day1"));
+
+        // A second day in the same year only adds to the year's module
+        NewDay::new(2015, 2).unwrap().generate(&root).unwrap();
+        assert_eq!(read("src/lib.rs").matches("pub mod y2015;").count(), 1);
+        assert_eq!(read("src/solution.rs").matches("y2015").count(), 1);
+        assert!(read("src/y2015/mod.rs").contains("pub mod day01;\npub mod day02;\n"));
+
+        // Nothing is overwritten, or written at all, if the day exists
+        fs::write(root.join("src/y2015/day01.rs"), "mine").unwrap();
+        assert!(new.generate(&root).unwrap_err().contains("day01.rs"));
+        assert_eq!(read("src/y2015/day01.rs"), "mine");
+        fs::remove_dir_all(&root).ok();
+    }
+}
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The marker in `src/lib.rs` and `src/solution.rs` that years are added in
+/// front of
+const YEARS: &str = "years";
+/// The marker in a year's `mod.rs` that day modules are added in front of
+const DAYS: &str = "days";
+/// The marker in a year's registry that days are added in front of
+const REGISTRY: &str = "registry";
+
+/// Adds `line` to the lines in front of the `// new-day: <marker>` line in
+/// `source`, with the same indentation, unless it's already there. It goes in
+/// sorted order, so the registered modules stay the way rustfmt has them.
+pub fn register(source: &str, marker: &str, line: &str) -> Result<String, String> {
+    let comment = format!("// new-day: {}", marker);
+    let lines: Vec<(usize, &str)> = source
+        .lines()
+        .scan(0, |offset, text| {
+            let start = *offset;
+            *offset += text.len() + 1;
+            Some((start, text))
+        })
+        .collect();
+    let at = lines
+        .iter()
+        .position(|(_, text)| text.trim() == comment)
+        .ok_or_else(|| format!("no {:?} marker", comment))?;
+    if lines.iter().any(|(_, text)| text.trim() == line) {
+        return Ok(source.to_string());
+    }
+    let marked = lines[at].1;
+    let indent = &marked[..marked.len() - marked.trim_start().len()];
+    // The lines already registered, right above the marker
+    let mut start = lines[at].0;
+    for &(offset, text) in lines[..at].iter().rev() {
+        if !text.starts_with(indent) || text.trim().is_empty() || text.trim() < line {
+            break;
+        }
+        start = offset;
+    }
+    let mut registered = source.to_string();
+    registered.insert_str(start, &format!("{}{}\n", indent, line));
+    Ok(registered)
+}
+
+/// A day to generate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NewDay {
+    pub year: u32,
+    pub day: u32,
+}
+
+impl NewDay {
+    /// Checks that `day` of `year` could be an Advent of Code puzzle
+    pub fn new(year: u32, day: u32) -> Result<NewDay, String> {
+        if year < 2015 {
+            return Err(format!("there's no Advent of Code in {}", year));
+        }
+        if !(1..=25).contains(&day) {
+            return Err(format!("day {} isn't between 1 and 25", day));
+        }
+        Ok(NewDay { year, day })
+    }
+    /// The day's module, like `day07`
+    pub fn module(&self) -> String {
+        format!("day{:02}", self.day)
+    }
+    /// The day's `Solution`, like `Day7`
+    pub fn name(&self) -> String {
+        format!("Day{}", self.day)
+    }
+    pub fn source(&self) -> PathBuf {
+        Path::new("src")
+            .join(format!("y{}", self.year))
+            .join(format!("{}.rs", self.module()))
+    }
+    /// Where the puzzle input goes, as registered
+    pub fn input(&self) -> String {
+        format!("input/{}/{}", self.year, self.module())
+    }
+    pub fn fixture(&self) -> PathBuf {
+        Path::new("fixtures")
+            .join(self.year.to_string())
+            .join(self.module())
+            .join("example.txt")
+    }
+    fn template(&self) -> String {
+        format!(
+            "\
+// Day {day} {year}
+
+#[cfg(test)]
+mod tests {{
+    use crate::y{year}::{module}::*;
+    #[test]
+    fn test_parse() {{
+        assert!({name}::parse(\"\").is_ok());
+    }}
+}}
+
+use crate::solution::{{Answer, Solution}};
+
+pub struct {name};
+
+impl Solution for {name} {{
+    type Input = String;
+    fn parse(input: &str) -> Result<String, String> {{
+        Ok(input.to_string())
+    }}
+    fn part1(_input: &String) -> Answer {{
+        Err(\"not solved yet\".to_string())
+    }}
+    fn part2(_input: &String) -> Answer {{
+        Err(\"not solved yet\".to_string())
+    }}
+}}
+",
+            day = self.day,
+            year = self.year,
+            module = self.module(),
+            name = self.name()
+        )
+    }
+    fn fixture_stub(&self) -> String {
+        "\
+# An example from the puzzle: uncomment the answers and put its input below
+# part1:
+# part2:
+---
+"
+        .to_string()
+    }
+    fn year_module(&self) -> String {
+        format!(
+            "\
+// Advent of Code {year}
+
+use crate::solution::Day;
+
+// new-day: {days}
+
+/// Every solved day of {year}, in order.
+pub fn registry() -> Vec<Day> {{
+    vec![
+        // new-day: {registry}
+    ]
+}}
+",
+            year = self.year,
+            days = DAYS,
+            registry = REGISTRY
+        )
+    }
+    /// Writes the day's files under `root`, and registers it. Fails before
+    /// writing anything if any of the files already exist.
+    pub fn generate<P: AsRef<Path>>(&self, root: P) -> Result<Vec<PathBuf>, String> {
+        let root = root.as_ref();
+        let files = vec![
+            (root.join(self.source()), self.template()),
+            (root.join(self.fixture()), self.fixture_stub()),
+            (root.join(self.input()), String::new()),
+        ];
+        let existing: Vec<String> = files
+            .iter()
+            .filter(|(path, _)| path.exists())
+            .map(|(path, _)| path.display().to_string())
+            .collect();
+        if !existing.is_empty() {
+            return Err(format!("won't overwrite {}", existing.join(", ")));
+        }
+
+        let edit = |path: &Path, marker: &str, line: &str| -> Result<(), String> {
+            let source = fs::read_to_string(path)
+                .map_err(|error| format!("couldn't read {}: {}", path.display(), error))?;
+            let registered = register(&source, marker, line)
+                .map_err(|error| format!("{}: {}", path.display(), error))?;
+            fs::write(path, registered)
+                .map_err(|error| format!("couldn't write {}: {}", path.display(), error))
+        };
+        let lib = root.join("src/lib.rs");
+        let solution = root.join("src/solution.rs");
+        let year = root.join(format!("src/y{}/mod.rs", self.year));
+        let new_year = !year.exists();
+        // Check the markers are there before creating anything
+        let markers = if new_year {
+            vec![(&lib, YEARS), (&solution, YEARS)]
+        } else {
+            vec![(&year, DAYS), (&year, REGISTRY)]
+        };
+        for (path, marker) in markers {
+            let source = fs::read_to_string(path)
+                .map_err(|error| format!("couldn't read {}: {}", path.display(), error))?;
+            register(&source, marker, "")
+                .map_err(|error| format!("{}: {}", path.display(), error))?;
+        }
+
+        let mut created = vec![];
+        if new_year {
+            write(&year, &self.year_module())?;
+            created.push(year.clone());
+        }
+        for (path, contents) in &files {
+            write(path, contents)?;
+            created.push(path.clone());
+        }
+        if new_year {
+            edit(&lib, YEARS, &format!("pub mod y{};", self.year))?;
+            edit(
+                &solution,
+                YEARS,
+                &format!("({0}, crate::y{0}::registry),", self.year),
+            )?;
+        }
+        edit(&year, DAYS, &format!("pub mod {};", self.module()))?;
+        edit(
+            &year,
+            REGISTRY,
+            &format!(
+                "Day::of::<{}::{}>({}, {}, {:?}),",
+                self.module(),
+                self.name(),
+                self.year,
+                self.day,
+                self.input()
+            ),
+        )?;
+        Ok(created)
+    }
+}
+
+/// Writes `contents` to `path`, creating its directory.
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("couldn't create {}: {}", parent.display(), error))?;
+    }
+    fs::write(path, contents)
+        .map_err(|error| format!("couldn't write {}: {}", path.display(), error))
+}
@@ -0,0 +1,202 @@
+// Checking every number in a range, spread over several threads
+
+#[cfg(test)]
+mod tests {
+    use crate::digits;
+    use crate::log::Progress;
+    use crate::scan::*;
+    #[test]
+    fn test_inclusive_bounds() {
+        assert_eq!(inclusive_bounds(&(3..7)), Some((3, 6)));
+        assert_eq!(inclusive_bounds(&(3..=7)), Some((3, 7)));
+        assert_eq!(inclusive_bounds(&(..)), Some((0, u64::MAX)));
+        assert_eq!(inclusive_bounds(&(3..3)), None);
+        assert_eq!(inclusive_bounds(&(0..0)), None);
+    }
+    #[test]
+    fn test_scan() {
+        let even = |n: u64| n % 2 == 0;
+        assert_eq!(scan(0..1000, even).count(), 500);
+        assert_eq!(scan(0..1000, even).threads(1).count(), 500);
+        assert_eq!(scan(0..=1000, even).chunk_size(7).count(), 501);
+        let progress = Progress::new("even", 1001);
+        scan(0..=1000, even)
+            .chunk_size(7)
+            .progress(&progress)
+            .count();
+        assert_eq!(progress.done(), 1001);
+        assert_eq!(scan(5..5, even).count(), 0);
+        let matches = scan(0..100_000, |n| n % 997 == 0).chunk_size(100).matches();
+        assert_eq!(matches, (0..100_000).step_by(997).collect::<Vec<_>>());
+        // The end of the u64s doesn't overflow
+        assert_eq!(scan(u64::MAX - 9.., even).count(), 5);
+    }
+    #[test]
+    fn test_scan_skip() {
+        let is_non_decreasing = |n: u64| {
+            digits::digits(n, 10)
+                .zip(digits::digits(n, 10).skip(1))
+                .all(|(a, b)| a <= b)
+        };
+        let skipping = scan(0..1_000_000, is_non_decreasing)
+            .skip_with(|n| digits::next_non_decreasing(n, 10))
+            .chunk_size(10_000);
+        let matches = skipping.matches();
+        assert_eq!(matches.len() as u64, skipping.count());
+        assert_eq!(
+            matches,
+            (0..1_000_000)
+                .filter(|&n| is_non_decreasing(n))
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+use crate::log::Progress;
+use std::ops::{Bound, RangeBounds};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// The smallest and largest numbers in `range`, or `None` if it's empty.
+pub fn inclusive_bounds<B: RangeBounds<u64>>(range: &B) -> Option<(u64, u64)> {
+    let lower = match range.start_bound() {
+        Bound::Included(&lower) => lower,
+        Bound::Excluded(&lower) => lower.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let upper = match range.end_bound() {
+        Bound::Included(&upper) => upper,
+        Bound::Excluded(&upper) => upper.checked_sub(1)?,
+        Bound::Unbounded => u64::MAX,
+    };
+    if lower > upper {
+        None
+    } else {
+        Some((lower, upper))
+    }
+}
+
+/// A jump to the next number worth checking, see `Scan::skip_with`.
+type Skip<'s> = Box<dyn Fn(u64) -> Option<u64> + Sync + 's>;
+
+/// Checks the numbers in `range` against `predicate`, in parallel.
+pub fn scan<'s, B: RangeBounds<u64>, P: Fn(u64) -> bool + Sync>(
+    range: B,
+    predicate: P,
+) -> Scan<'s, P> {
+    Scan {
+        bounds: inclusive_bounds(&range),
+        predicate,
+        threads: thread::available_parallelism().map_or(1, |n| n.get()),
+        chunk_size: 1 << 16,
+        skip: None,
+        progress: None,
+    }
+}
+
+/// A scan over a range, see `scan`. The range is cut into chunks, which the
+/// threads take one at a time as they finish, so a slow chunk doesn't hold
+/// up the rest.
+pub struct Scan<'s, P> {
+    bounds: Option<(u64, u64)>,
+    predicate: P,
+    threads: usize,
+    chunk_size: u64,
+    skip: Option<Skip<'s>>,
+    progress: Option<&'s Progress>,
+}
+
+impl<'s, P: Fn(u64) -> bool + Sync> Scan<'s, P> {
+    /// Use this many threads instead of one per core
+    pub fn threads(mut self, threads: usize) -> Scan<'s, P> {
+        self.threads = std::cmp::max(threads, 1);
+        self
+    }
+    /// Have each thread take this many numbers at a time
+    pub fn chunk_size(mut self, chunk_size: u64) -> Scan<'s, P> {
+        self.chunk_size = std::cmp::max(chunk_size, 1);
+        self
+    }
+    /// Jump from each number to `skip(number)`, the smallest number at least
+    /// as large that could match, or `None` if none can. For rules like
+    /// day 4's, where most numbers can be ruled out in blocks.
+    pub fn skip_with<F: Fn(u64) -> Option<u64> + Sync + 's>(mut self, skip: F) -> Scan<'s, P> {
+        self.skip = Some(Box::new(skip));
+        self
+    }
+    /// Advance `progress` by each chunk as it's scanned
+    pub fn progress(mut self, progress: &'s Progress) -> Scan<'s, P> {
+        self.progress = Some(progress);
+        self
+    }
+    /// How many numbers match
+    pub fn count(&self) -> u64 {
+        self.run(false).0
+    }
+    /// The numbers that match, smallest first
+    pub fn matches(&self) -> Vec<u64> {
+        self.run(true).1
+    }
+    /// Scans every chunk, counting matches and collecting them if `collect`
+    fn run(&self, collect: bool) -> (u64, Vec<u64>) {
+        let (lower, upper) = match self.bounds {
+            Some(bounds) => bounds,
+            None => return (0, vec![]),
+        };
+        let chunks = ((upper - lower) / self.chunk_size).saturating_add(1);
+        let next_chunk = AtomicU64::new(0);
+        let count = AtomicU64::new(0);
+        // Each chunk's matches, by chunk, to put back in order at the end
+        let found = Mutex::new(vec![]);
+        thread::scope(|scope| {
+            for _ in 0..self.threads {
+                scope.spawn(|| loop {
+                    let chunk = next_chunk.fetch_add(1, Ordering::Relaxed);
+                    if chunk >= chunks {
+                        break;
+                    }
+                    let start = lower + chunk * self.chunk_size;
+                    let end = std::cmp::min(upper, start.saturating_add(self.chunk_size - 1));
+                    let matches = self.scan_chunk(start, end, collect);
+                    count.fetch_add(matches.0, Ordering::Relaxed);
+                    if let Some(progress) = self.progress {
+                        progress.advance(end - start + 1);
+                    }
+                    if collect && !matches.1.is_empty() {
+                        found.lock().unwrap().push((chunk, matches.1));
+                    }
+                });
+            }
+        });
+        let mut found = found.into_inner().unwrap();
+        found.sort_unstable_by_key(|&(chunk, _)| chunk);
+        let matches = found.into_iter().flat_map(|(_, matches)| matches).collect();
+        (count.into_inner(), matches)
+    }
+    /// Scans `start..=end` on this thread
+    fn scan_chunk(&self, start: u64, end: u64, collect: bool) -> (u64, Vec<u64>) {
+        let mut count = 0;
+        let mut matches = vec![];
+        let mut number = start;
+        loop {
+            if let Some(skip) = &self.skip {
+                number = match skip(number) {
+                    Some(next) if next <= end => next,
+                    _ => break,
+                };
+            }
+            if (self.predicate)(number) {
+                count += 1;
+                if collect {
+                    matches.push(number);
+                }
+            }
+            if number == end {
+                break;
+            }
+            number += 1;
+        }
+        (count, matches)
+    }
+}
@@ -0,0 +1,435 @@
+// Shortest paths with BFS, Dijkstra, and A*
+//
+// Anything with successors is a `Graph`: a `Grid` of passable cells, or a
+// closure from a node to its neighbors and the cost of stepping to each one.
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Point;
+    use crate::grid::Grid;
+    use crate::search::*;
+    const MAZE: &str = "\
+S...#....
+.##.#.##.
+.#..#..#.
+.#.###.#.
+.......#G
+";
+    fn maze() -> (Grid<char>, Point, Point) {
+        let grid = Grid::parse(MAZE).unwrap();
+        let start = grid.iter().find(|(_, &c)| c == 'S').unwrap().0;
+        let goal = grid.iter().find(|(_, &c)| c == 'G').unwrap().0;
+        (grid, start, goal)
+    }
+    #[test]
+    fn test_bfs() {
+        let (grid, start, goal) = maze();
+        let path = bfs(&grid, vec![start], |&p| p == goal).unwrap();
+        assert_eq!(path.cost, 22);
+        assert_eq!(path.nodes.len(), 23);
+        assert_eq!(path.nodes.first(), Some(&start));
+        assert_eq!(path.nodes.last(), Some(&goal));
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|step| step[0].manhattan(step[1]) == 1));
+        assert_eq!(bfs(&grid, vec![start], |&p| p == Point::new(4, 0)), None);
+    }
+    #[test]
+    fn test_dijkstra_and_astar_agree() {
+        let (grid, start, goal) = maze();
+        let dijkstra = dijkstra(&grid, vec![start], |&p| p == goal).unwrap();
+        let astar = astar(
+            &grid,
+            vec![start],
+            |&p| p == goal,
+            manhattan_heuristic(goal),
+        )
+        .unwrap();
+        assert_eq!(dijkstra.cost, 22);
+        assert_eq!(astar.cost, 22);
+        assert!(astar.visited <= dijkstra.visited);
+    }
+    #[test]
+    fn test_weighted_closure() {
+        // Stepping up costs 2, doubling costs 3, so 1 -> 10 is 1, 2, 4, 5, 10
+        let graph = |&n: &usize| vec![(n + 1, 2), (n * 2, 3)];
+        let path = dijkstra(&graph, vec![1], |&n| n == 10).unwrap();
+        assert_eq!(path.cost, 10);
+        assert_eq!(path.nodes, vec![1, 2, 4, 5, 10]);
+    }
+    #[test]
+    fn test_multi_source() {
+        let (grid, _, goal) = maze();
+        let starts = vec![Point::new(0, 0), Point::new(8, 0)];
+        let path = bfs(&grid, starts, |&p| p == goal).unwrap();
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.nodes.first(), Some(&Point::new(8, 0)));
+    }
+    #[test]
+    fn test_distances() {
+        let graph = |&n: &u32| if n < 5 { vec![(n + 1, 2)] } else { vec![] };
+        let distances = distances(&graph, vec![0]);
+        assert_eq!(distances.len(), 6);
+        assert_eq!(distances[&5], 10);
+    }
+    #[test]
+    fn test_all_shortest_paths() {
+        let grid = Grid::parse("...\n...\n").unwrap();
+        let goal = Point::new(2, 1);
+        let paths = all_shortest_paths(&grid, vec![Point::new(0, 0)], |&p| p == goal).unwrap();
+        assert_eq!(paths.cost, 3);
+        assert_eq!(paths.paths.len(), 3);
+        assert!(paths.paths.iter().all(|path| path.len() == 4));
+        // Free cells tie with each other, but mustn't become each other's parents
+        let free = Grid::from_rows(vec![vec![1, 0, 0, 1]]).unwrap();
+        let goal = Point::new(3, 0);
+        let paths = all_shortest_paths(&free, vec![Point::origin()], |&p| p == goal).unwrap();
+        assert_eq!(paths.cost, 1);
+        assert_eq!(
+            paths.paths,
+            vec![(0..4).map(|x| Point::new(x, 0)).collect::<Vec<_>>()]
+        );
+        let path = dijkstra(&free, vec![Point::origin()], |&p| p == goal).unwrap();
+        assert_eq!(path.nodes.len(), 4);
+    }
+}
+
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::y2019::day03::manhattan_distance;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Something that can be searched for paths between nodes of type `N`.
+pub trait Graph<N> {
+    /// The nodes one step away from `node`, with the cost of each step
+    fn successors(&self, node: &N) -> Vec<(N, usize)>;
+}
+
+impl<N, F, I> Graph<N> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    fn successors(&self, node: &N) -> Vec<(N, usize)> {
+        self(node).into_iter().collect()
+    }
+}
+
+/// A grid cell that can be walked through.
+pub trait Passable {
+    /// The cost of stepping onto this cell, or `None` if it is a wall
+    fn cost(&self) -> Option<usize>;
+}
+
+impl Passable for char {
+    /// `#` is a wall, everything else costs 1
+    fn cost(&self) -> Option<usize> {
+        match self {
+            '#' => None,
+            _ => Some(1),
+        }
+    }
+}
+
+impl Passable for bool {
+    /// `true` is open, `false` is a wall
+    fn cost(&self) -> Option<usize> {
+        if *self {
+            Some(1)
+        } else {
+            None
+        }
+    }
+}
+
+impl Passable for usize {
+    /// The cell is its own cost
+    fn cost(&self) -> Option<usize> {
+        Some(*self)
+    }
+}
+
+impl<T: Passable> Graph<Point> for Grid<T> {
+    fn successors(&self, node: &Point) -> Vec<(Point, usize)> {
+        self.neighbors4(*node)
+            .filter_map(|neighbor| self[neighbor].cost().map(|cost| (neighbor, cost)))
+            .collect()
+    }
+}
+
+/// A shortest path found by a search.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Path<N> {
+    /// The nodes of the path, from the start to the goal
+    pub nodes: Vec<N>,
+    /// The total cost of the path
+    pub cost: usize,
+    /// The number of nodes expanded to find the path
+    pub visited: usize,
+}
+
+/// Every shortest path found by a search.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Paths<N> {
+    /// Each path, from a start to a goal
+    pub paths: Vec<Vec<N>>,
+    /// The cost shared by every path
+    pub cost: usize,
+    /// The number of nodes expanded to find the paths
+    pub visited: usize,
+}
+
+/// The nodes a search has seen, interned so the heap doesn't need `N: Ord`.
+struct Seen<N> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    cost: Vec<usize>,
+    parents: Vec<Vec<usize>>,
+}
+
+impl<N: Clone + Eq + Hash> Seen<N> {
+    fn new() -> Seen<N> {
+        Seen {
+            nodes: vec![],
+            index: HashMap::new(),
+            cost: vec![],
+            parents: vec![],
+        }
+    }
+    /// Record reaching `node` with `cost` from `parent`. Returns the node's
+    /// index if this is the cheapest way to reach it so far.
+    fn reach(
+        &mut self,
+        node: N,
+        cost: usize,
+        parent: Option<usize>,
+        keep_ties: bool,
+    ) -> Option<usize> {
+        let parents = parent.into_iter().collect();
+        match self.index.get(&node) {
+            None => {
+                let i = self.nodes.len();
+                self.index.insert(node.clone(), i);
+                self.nodes.push(node);
+                self.cost.push(cost);
+                self.parents.push(parents);
+                Some(i)
+            }
+            Some(&i) if cost < self.cost[i] => {
+                self.cost[i] = cost;
+                self.parents[i] = parents;
+                Some(i)
+            }
+            Some(&i) => {
+                // Zero cost steps can tie a node with its own descendants, which
+                // would make the paths to it go round in circles
+                if keep_ties && cost == self.cost[i] {
+                    let parent = parent.filter(|&parent| !self.is_ancestor(i, parent));
+                    self.parents[i].extend(parent);
+                }
+                None
+            }
+        }
+    }
+    /// Whether the node at index `ancestor` is on some path to index `i`
+    fn is_ancestor(&self, ancestor: usize, i: usize) -> bool {
+        let mut stack = vec![i];
+        let mut checked = vec![false; self.nodes.len()];
+        while let Some(i) = stack.pop() {
+            if i == ancestor {
+                return true;
+            }
+            if !checked[i] {
+                checked[i] = true;
+                stack.extend(&self.parents[i]);
+            }
+        }
+        false
+    }
+    /// The path from a start to the node at index `i`, following first parents
+    fn path_to(&self, mut i: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[i].clone()];
+        while let Some(&parent) = self.parents[i].first() {
+            path.push(self.nodes[parent].clone());
+            i = parent;
+        }
+        path.reverse();
+        path
+    }
+    /// Every path from a start to the node at index `i`, following every parent
+    fn paths_to(&self, i: usize) -> Vec<Vec<N>> {
+        if self.parents[i].is_empty() {
+            return vec![vec![self.nodes[i].clone()]];
+        }
+        let mut paths = vec![];
+        for &parent in &self.parents[i] {
+            for mut path in self.paths_to(parent) {
+                path.push(self.nodes[i].clone());
+                paths.push(path);
+            }
+        }
+        paths
+    }
+}
+
+/// Breadth-first search from any of `starts` to the first node satisfying
+/// `is_goal`, ignoring step costs. The cost of the path is its number of steps.
+pub fn bfs<N, G, S, F>(graph: &G, starts: S, mut is_goal: F) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> bool,
+{
+    let mut seen = Seen::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Some(i) = seen.reach(start, 0, None, false) {
+            queue.push_back(i);
+        }
+    }
+    let mut visited = 0;
+    while let Some(i) = queue.pop_front() {
+        visited += 1;
+        if is_goal(&seen.nodes[i]) {
+            return Some(Path {
+                nodes: seen.path_to(i),
+                cost: seen.cost[i],
+                visited,
+            });
+        }
+        let cost = seen.cost[i] + 1;
+        for (next, _) in graph.successors(&seen.nodes[i]) {
+            if !seen.index.contains_key(&next) {
+                let j = seen.reach(next, cost, Some(i), false).unwrap();
+                queue.push_back(j);
+            }
+        }
+    }
+    None
+}
+
+/// Best-first search guided by `heuristic`, stopping once every node cheaper
+/// than the first goal has been expanded. Returns the search state and the
+/// indices of every goal reached at the cheapest cost.
+fn best_first<N, G, S, F, H>(
+    graph: &G,
+    starts: S,
+    mut is_goal: F,
+    heuristic: H,
+    keep_ties: bool,
+) -> (Seen<N>, Vec<usize>, usize)
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> bool,
+    H: Fn(&N) -> usize,
+{
+    let mut seen = Seen::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if let Some(i) = seen.reach(start, 0, None, keep_ties) {
+            heap.push(Reverse((heuristic(&seen.nodes[i]), 0, i)));
+        }
+    }
+    let mut goals = vec![];
+    let mut visited = 0;
+    while let Some(Reverse((estimate, cost, i))) = heap.pop() {
+        // Skip entries made stale by finding a cheaper way to the node
+        if cost > seen.cost[i] {
+            continue;
+        }
+        if let Some(&goal) = goals.first() {
+            if estimate > seen.cost[goal] {
+                break;
+            }
+        }
+        visited += 1;
+        if is_goal(&seen.nodes[i]) {
+            goals.push(i);
+            if !keep_ties {
+                break;
+            }
+            continue;
+        }
+        for (next, step) in graph.successors(&seen.nodes[i]) {
+            let next_cost = cost + step;
+            if let Some(j) = seen.reach(next, next_cost, Some(i), keep_ties) {
+                heap.push(Reverse((
+                    next_cost + heuristic(&seen.nodes[j]),
+                    next_cost,
+                    j,
+                )));
+            }
+        }
+    }
+    (seen, goals, visited)
+}
+
+/// Dijkstra's algorithm from any of `starts` to the cheapest node satisfying
+/// `is_goal`.
+pub fn dijkstra<N, G, S, F>(graph: &G, starts: S, is_goal: F) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> bool,
+{
+    astar(graph, starts, is_goal, |_| 0)
+}
+
+/// A* from any of `starts` to the cheapest node satisfying `is_goal`.
+/// `heuristic` must never overestimate the cost to the nearest goal.
+pub fn astar<N, G, S, F, H>(graph: &G, starts: S, is_goal: F, heuristic: H) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> bool,
+    H: Fn(&N) -> usize,
+{
+    let (seen, goals, visited) = best_first(graph, starts, is_goal, heuristic, false);
+    let goal = *goals.first()?;
+    Some(Path {
+        nodes: seen.path_to(goal),
+        cost: seen.cost[goal],
+        visited,
+    })
+}
+
+/// Every cheapest path from any of `starts` to any node satisfying `is_goal`.
+pub fn all_shortest_paths<N, G, S, F>(graph: &G, starts: S, is_goal: F) -> Option<Paths<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> bool,
+{
+    let (seen, goals, visited) = best_first(graph, starts, is_goal, |_| 0, true);
+    let cost = seen.cost[*goals.first()?];
+    Some(Paths {
+        paths: goals.iter().flat_map(|&goal| seen.paths_to(goal)).collect(),
+        cost,
+        visited,
+    })
+}
+
+/// The cost of the cheapest path to every node reachable from `starts`.
+pub fn distances<N, G, S>(graph: &G, starts: S) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    S: IntoIterator<Item = N>,
+{
+    let (seen, _, _) = best_first(graph, starts, |_| false, |_| 0, false);
+    seen.nodes.into_iter().zip(seen.cost).collect()
+}
+
+/// An A* heuristic for orthogonal grids with steps costing at least 1.
+pub fn manhattan_heuristic(goal: Point) -> impl Fn(&Point) -> usize {
+    move |&point| manhattan_distance(point, goal)
+}
@@ -0,0 +1,184 @@
+// Every day's solution behind one interface, so they can be run and timed
+// together
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::*;
+    #[test]
+    fn test_registry() {
+        let days = registry();
+        assert_eq!(
+            days.iter()
+                .map(|day| (day.year, day.day))
+                .collect::<Vec<_>>(),
+            vec![(2019, 1), (2019, 2), (2019, 3), (2019, 4)]
+        );
+        assert_eq!(year(2019).len(), 4);
+        assert!(year(2015).is_empty());
+        assert!(find(2019, 4).is_some());
+        assert!(find(2019, 25).is_none());
+    }
+    #[test]
+    fn test_day_solve() {
+        let day = find(2019, 1).unwrap();
+        let parsed = day.parse("12\n14\n1969\n100756\n").unwrap();
+        assert_eq!(day.solve(&parsed, Part::One), Ok("34241".to_string()));
+        assert_eq!(day.solve(&parsed, Part::Two), Ok("51316".to_string()));
+        assert!(day.parse("12\nfourteen\n").is_err());
+
+        let day = find(2019, 3).unwrap();
+        let parsed = day
+            .parse("R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83\n")
+            .unwrap();
+        assert_eq!(day.solve(&parsed, Part::One), Ok("159".to_string()));
+        assert_eq!(day.solve(&parsed, Part::Two), Ok("610".to_string()));
+
+        let day = find(2019, 4).unwrap();
+        let parsed = day.parse("165432-707912").unwrap();
+        assert_eq!(day.solve(&parsed, Part::One), Ok("1716".to_string()));
+        assert_eq!(day.solve(&parsed, Part::Two), Ok("1163".to_string()));
+    }
+}
+
+use crate::y2019;
+use std::any::Any;
+use std::fmt;
+use std::fs;
+use std::io;
+
+/// A part's answer, or why there isn't one.
+pub type Answer = Result<String, String>;
+
+/// A day's puzzle: how to read its input, and how to answer each part.
+pub trait Solution {
+    /// The parsed puzzle input
+    type Input;
+    fn parse(input: &str) -> Result<Self::Input, String>;
+    fn part1(input: &Self::Input) -> Answer;
+    fn part2(input: &Self::Input) -> Answer;
+}
+
+/// One of the two parts of a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// A parsed puzzle input, whatever its type, see `Day::parse`.
+pub struct Parsed(Box<dyn Any>);
+
+/// A `Solution` without its input type, so every day fits in one list.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    /// Where the puzzle input is, relative to the repository
+    pub input: &'static str,
+    parse: fn(&str) -> Result<Parsed, String>,
+    solve: fn(&Parsed, Part) -> Answer,
+}
+
+fn parse_erased<S: Solution>(input: &str) -> Result<Parsed, String>
+where
+    S::Input: 'static,
+{
+    S::parse(input).map(|input| Parsed(Box::new(input)))
+}
+
+fn solve_erased<S: Solution>(parsed: &Parsed, part: Part) -> Answer
+where
+    S::Input: 'static,
+{
+    let input = parsed
+        .0
+        .downcast_ref::<S::Input>()
+        .expect("input parsed by a different day");
+    match part {
+        Part::One => S::part1(input),
+        Part::Two => S::part2(input),
+    }
+}
+
+impl Day {
+    /// The day solved by `S`, with its input at `input`
+    pub fn of<S: Solution>(year: u32, day: u32, input: &'static str) -> Day
+    where
+        S::Input: 'static,
+    {
+        Day {
+            year,
+            day,
+            input,
+            parse: parse_erased::<S>,
+            solve: solve_erased::<S>,
+        }
+    }
+    /// Reads the puzzle input from `self.input`
+    pub fn read_input(&self) -> io::Result<String> {
+        fs::read_to_string(self.input)
+    }
+    pub fn parse(&self, input: &str) -> Result<Parsed, String> {
+        (self.parse)(input)
+    }
+    /// Answers `part` for an input parsed by this day
+    ///
+    /// Panics if another day parsed the input.
+    pub fn solve(&self, parsed: &Parsed, part: Part) -> Answer {
+        (self.solve)(parsed, part)
+    }
+}
+
+impl fmt::Debug for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day({} day {}, {:?})", self.year, self.day, self.input)
+    }
+}
+
+/// A year's days, in order.
+pub type Registry = fn() -> Vec<Day>;
+
+/// Every year with solutions, and its registry.
+const YEARS: &[(u32, Registry)] = &[
+    (2019, y2019::registry),
+    // new-day: years
+];
+
+/// Every solved day of `year`, in order.
+pub fn year(year: u32) -> Vec<Day> {
+    YEARS
+        .iter()
+        .filter(|&&(found, _)| found == year)
+        .flat_map(|(_, registry)| registry())
+        .collect()
+}
+
+/// Every solved day, in order.
+pub fn registry() -> Vec<Day> {
+    let mut days: Vec<Day> = YEARS.iter().flat_map(|(_, registry)| registry()).collect();
+    days.sort_by_key(|day| (day.year, day.day));
+    days
+}
+
+/// The solution for `day` of `year`, if there is one.
+pub fn find(year: u32, day: u32) -> Option<Day> {
+    self::year(year)
+        .into_iter()
+        .find(|found| found.year == year && found.day == day)
+}
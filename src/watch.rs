@@ -0,0 +1,224 @@
+// Noticing when a day's files change, and what changed in its answers
+//
+// Only polls modification times, so it works anywhere std's filesystem does.
+
+#[cfg(test)]
+mod tests {
+    use crate::solution;
+    use crate::watch::*;
+    use std::env;
+    use std::time::{Duration, SystemTime};
+    #[test]
+    fn test_watched() {
+        let day = solution::find(2019, 3).unwrap();
+        assert_eq!(
+            watched(&day),
+            vec![
+                PathBuf::from("src/y2019/day03.rs"),
+                PathBuf::from("fixtures/2019/day03"),
+                PathBuf::from("input/day3"),
+            ]
+        );
+    }
+    #[test]
+    fn test_watcher() {
+        let root = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::remove_dir_all(&root).ok();
+        fs::create_dir_all(root.join("fixtures")).unwrap();
+        let source = root.join("day01.rs");
+        let fixture = root.join("fixtures/example.txt");
+        fs::write(&source, "").unwrap();
+        let mut watcher = Watcher::new(vec![source.clone(), root.join("fixtures")]);
+        assert!(watcher.changed().is_empty());
+
+        // New files in a watched directory count, as do edits and removals
+        fs::write(&fixture, "").unwrap();
+        assert_eq!(watcher.changed(), vec![fixture.clone()]);
+        let later = SystemTime::now() + Duration::from_secs(5);
+        fs::File::options()
+            .write(true)
+            .open(&source)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_eq!(watcher.changed(), vec![source.clone()]);
+        fs::remove_file(&fixture).unwrap();
+        assert_eq!(watcher.changed(), vec![fixture]);
+        assert!(watcher.changed().is_empty());
+        fs::remove_dir_all(&root).ok();
+    }
+    #[test]
+    fn test_answers() {
+        let tsv = "year\tday\tpart\tvariant\tstatus\tanswer\tparse_ns\tsolve_ns\n\
+                   2019\t3\t1\t\tok\t159\t10\t20\n\
+                   2019\t3\t2\tslow\terror\tno wires\t10\t20\n";
+        assert_eq!(
+            answers(tsv),
+            vec![
+                ("part 1".to_string(), Ok("159".to_string())),
+                ("part 2 (slow)".to_string(), Err("no wires".to_string())),
+            ]
+        );
+        assert!(answers("").is_empty());
+    }
+    #[test]
+    fn test_diff() {
+        let answer = |label: &str, answer: Answer| (label.to_string(), answer);
+        let previous = vec![
+            answer("part 1", Ok("159".to_string())),
+            answer("part 2", Err("not solved yet".to_string())),
+        ];
+        let current = vec![
+            answer("part 1", Ok("159".to_string())),
+            answer("part 2", Ok("610".to_string())),
+        ];
+        assert_eq!(
+            diff(&previous, &current),
+            vec![
+                "part 1: 159 (unchanged)",
+                "part 2: 610 (was failing: not solved yet)"
+            ]
+        );
+        assert_eq!(
+            diff(&current, &previous),
+            vec![
+                "part 1: 159 (unchanged)",
+                "part 2 failed: not solved yet (was 610)"
+            ]
+        );
+        let changed = vec![answer("part 1", Ok("160".to_string()))];
+        assert_eq!(
+            diff(&previous, &changed),
+            vec!["part 1: 160 (was 159)", "part 2: gone"]
+        );
+        assert_eq!(diff(&[], &changed), vec!["part 1: 160 (new)"]);
+    }
+}
+
+use crate::scaffold::NewDay;
+use crate::solution::{Answer, Day};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// The files to watch for `day`: its module, its fixtures, and its input.
+pub fn watched(day: &Day) -> Vec<PathBuf> {
+    let paths = NewDay {
+        year: day.year,
+        day: day.day,
+    };
+    let fixtures = paths.fixture().parent().map(PathBuf::from);
+    let mut watched = vec![paths.source()];
+    watched.extend(fixtures);
+    watched.push(PathBuf::from(day.input));
+    watched
+}
+
+/// When each file under `paths` was last modified, looking inside
+/// directories. Missing paths are left out.
+fn snapshot(paths: &[PathBuf]) -> HashMap<PathBuf, SystemTime> {
+    let mut modified = HashMap::new();
+    let mut paths = paths.to_vec();
+    while let Some(path) = paths.pop() {
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(&path) {
+                paths.extend(entries.filter_map(Result::ok).map(|entry| entry.path()));
+            }
+        } else if let Ok(time) = metadata.modified() {
+            modified.insert(path, time);
+        }
+    }
+    modified
+}
+
+/// Polls some files and directories for changes.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    seen: HashMap<PathBuf, SystemTime>,
+}
+
+impl Watcher {
+    /// Watches `paths`, starting from how they are now
+    pub fn new(paths: Vec<PathBuf>) -> Watcher {
+        let seen = snapshot(&paths);
+        Watcher { paths, seen }
+    }
+    /// The files added, modified, or removed since the last call, sorted
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let now = snapshot(&self.paths);
+        let mut changed: Vec<PathBuf> = now
+            .iter()
+            .filter(|&(path, time)| self.seen.get(path) != Some(time))
+            .map(|(path, _)| path.clone())
+            .chain(
+                self.seen
+                    .keys()
+                    .filter(|path| !now.contains_key(*path))
+                    .cloned(),
+            )
+            .collect();
+        changed.sort();
+        self.seen = now;
+        changed
+    }
+}
+
+/// The answers in `aoc run --format tsv` output, labelled like
+/// `part 1 (brute force)`.
+pub fn answers(tsv: &str) -> Vec<(String, Answer)> {
+    tsv.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 6 {
+                return None;
+            }
+            let label = match fields[3] {
+                "" => format!("part {}", fields[2]),
+                variant => format!("part {} ({})", fields[2], variant),
+            };
+            let answer = match fields[4] {
+                "ok" => Ok(fields[5].to_string()),
+                _ => Err(fields[5].to_string()),
+            };
+            Some((label, answer))
+        })
+        .collect()
+}
+
+/// A line for each current answer, saying how it differs from the previous
+/// run, then a line for each answer that's gone.
+pub fn diff(previous: &[(String, Answer)], current: &[(String, Answer)]) -> Vec<String> {
+    let before: HashMap<&str, &Answer> = previous
+        .iter()
+        .map(|(label, answer)| (label.as_str(), answer))
+        .collect();
+    let mut lines: Vec<String> = current
+        .iter()
+        .map(|(label, answer)| {
+            let now = match answer {
+                Ok(answer) => format!("{}: {}", label, answer),
+                Err(error) => format!("{} failed: {}", label, error),
+            };
+            let was = match before.get(label.as_str()) {
+                None => "new".to_string(),
+                Some(&old) if old == answer => "unchanged".to_string(),
+                Some(Ok(old)) => format!("was {}", old),
+                Some(Err(error)) => format!("was failing: {}", error),
+            };
+            format!("{} ({})", now, was)
+        })
+        .collect();
+    lines.extend(
+        previous
+            .iter()
+            .filter(|(label, _)| current.iter().all(|(now, _)| now != label))
+            .map(|(label, _)| format!("{}: gone", label)),
+    );
+    lines
+}
@@ -0,0 +1,363 @@
+// Day 1 2019
+
+#[cfg(test)]
+mod tests {
+    use crate::y2019::day01::*;
+    #[test]
+    fn test_fuel_from_mass() {
+        assert_eq!(fuel_from_mass(12), 2);
+        assert_eq!(fuel_from_mass(14), 2);
+        assert_eq!(fuel_from_mass(1969), 654);
+        assert_eq!(fuel_from_mass(100756), 33583);
+    }
+    #[test]
+    fn test_fuel_for_module() {
+        assert_eq!(fuel_for_module(14), 2);
+        assert_eq!(fuel_for_module(1969), 966);
+        assert_eq!(fuel_for_module(100756), 50346);
+    }
+    #[test]
+    fn test_fuel_chain() {
+        let chain: Vec<usize> = FuelModel::STANDARD.fuel_chain(1969).collect();
+        assert_eq!(chain, vec![654, 216, 70, 21, 5]);
+        assert_eq!(FuelModel::STANDARD.fuel_chain(2).count(), 0);
+    }
+    #[test]
+    fn test_custom_model() {
+        // Halve and subtract one, ignoring anything 3 or less
+        let model = FuelModel::new(2, 1, 3);
+        assert_eq!(model.fuel_from_mass(100), 49);
+        assert_eq!(model.fuel_from_mass(8), 0);
+        let chain: Vec<usize> = model.fuel_chain(100).collect();
+        assert_eq!(chain, vec![49, 23, 10, 4]);
+        assert_eq!(model.fuel_for_module(100), 86);
+    }
+    #[test]
+    #[should_panic]
+    fn test_model_must_shrink() {
+        FuelModel::new(1, 0, 0);
+    }
+    #[test]
+    fn test_breakdown() {
+        let masses = [12, 14, 1969, 100756];
+        let breakdown = FuelModel::STANDARD.breakdown(&masses);
+        assert_eq!(breakdown.len(), 4);
+        assert_eq!(
+            breakdown[2],
+            ModuleFuel {
+                mass: 1969,
+                stages: vec![654, 216, 70, 21, 5],
+            }
+        );
+        assert_eq!(breakdown[2].fuel(), 654);
+        assert_eq!(breakdown[2].total(), 966);
+        let totals = FuelModel::STANDARD.totals(&masses);
+        assert_eq!(totals, (34241, 51316));
+    }
+    #[test]
+    fn test_checked() {
+        // Subtracting a little each stage adds up to about mass^2 / 2
+        let model = FuelModel::new(1, 1, 0);
+        assert_eq!(model.checked_fuel_for_module(5), Ok(4 + 3 + 2 + 1));
+        let model = FuelModel::new(1, 1 << 50, 0);
+        assert_eq!(
+            model.checked_fuel_for_module(usize::MAX),
+            Err(FuelOverflow {
+                mass: usize::MAX as u128
+            })
+        );
+        assert_eq!(model.saturating_fuel_for_module(usize::MAX), usize::MAX);
+        let standard = FuelModel::STANDARD;
+        assert_eq!(standard.checked_totals(&[12, 1969]), Ok((656, 968)));
+        assert!(standard
+            .checked_totals(&[usize::MAX, usize::MAX, usize::MAX, usize::MAX])
+            .is_err());
+        assert_eq!(
+            standard.saturating_totals(&[usize::MAX; 4]),
+            (usize::MAX, usize::MAX)
+        );
+    }
+    #[test]
+    fn test_wide() {
+        let standard = FuelModel::STANDARD;
+        assert_eq!(standard.wide_fuel_for_module(100756), Ok(50346));
+        let mass = 99_999_999_999_999_999_999;
+        assert_eq!(
+            standard.wide_fuel_from_mass(mass),
+            33_333_333_333_333_333_331
+        );
+        let mut totals = FuelTotals::new();
+        for _ in 0..10 {
+            totals.add(&standard, mass).unwrap();
+        }
+        assert_eq!(totals.fuel.to_string(), "333333333333333333310");
+        assert_eq!(
+            totals.total,
+            BigUint::from(10 * standard.wide_fuel_for_module(mass).unwrap())
+        );
+    }
+}
+
+use crate::bigint::BigUint;
+use crate::input;
+use crate::solution::{Answer, Solution};
+use std::fmt;
+
+/// A variant of the rocket equation, where the fuel for a mass is the mass
+/// divided by `divisor` (rounding down) minus `subtrahend`. Fuel amounts at or
+/// below `floor` are treated as no fuel at all.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FuelModel {
+    divisor: usize,
+    subtrahend: usize,
+    floor: usize,
+}
+
+impl FuelModel {
+    /// The model from the puzzle: divide by three, round down, and subtract 2
+    pub const STANDARD: FuelModel = FuelModel {
+        divisor: 3,
+        subtrahend: 2,
+        floor: 0,
+    };
+
+    /// Panics if the model wouldn't always need less fuel than mass, because
+    /// then the fuel for fuel would never run out.
+    pub fn new(divisor: usize, subtrahend: usize, floor: usize) -> FuelModel {
+        if divisor == 0 || (divisor == 1 && subtrahend == 0) {
+            panic!(
+                "Fuel must be less than mass, but divisor was {} and subtrahend was {}",
+                divisor, subtrahend
+            );
+        }
+        FuelModel {
+            divisor,
+            subtrahend,
+            floor,
+        }
+    }
+
+    /// Gets the amount of fuel required for a mass, not accounting for fuel.
+    pub fn fuel_from_mass(&self, mass: usize) -> usize {
+        // Fuel is always less than mass, so it fits back into a usize
+        self.wide_fuel_from_mass(mass as u128) as usize
+    }
+
+    /// Gets the amount of fuel required for a mass too big for a usize.
+    pub fn wide_fuel_from_mass(&self, mass: u128) -> u128 {
+        let fuel = (mass / self.divisor as u128).saturating_sub(self.subtrahend as u128);
+        if fuel > self.floor as u128 {
+            fuel
+        } else {
+            0
+        }
+    }
+
+    /// Each stage of fuel needed for a mass: the fuel for the mass, then the
+    /// fuel for that fuel, and so on until no more fuel is needed.
+    pub fn fuel_chain(&self, mass: usize) -> FuelChain {
+        FuelChain { model: *self, mass }
+    }
+
+    /// Gets the amount of fuel required for a module *accounting for fuel*
+    pub fn fuel_for_module(&self, mass: usize) -> usize {
+        self.fuel_chain(mass).sum()
+    }
+
+    /// Like `fuel_for_module`, but returns an error instead of overflowing.
+    pub fn checked_fuel_for_module(&self, mass: usize) -> Result<usize, FuelOverflow> {
+        self.fuel_chain(mass)
+            .try_fold(0usize, |total, fuel| total.checked_add(fuel))
+            .ok_or(FuelOverflow { mass: mass as u128 })
+    }
+
+    /// Like `fuel_for_module`, but stops at `usize::MAX` instead of
+    /// overflowing.
+    pub fn saturating_fuel_for_module(&self, mass: usize) -> usize {
+        let mut total: usize = 0;
+        for fuel in self.fuel_chain(mass) {
+            total = total.saturating_add(fuel);
+            if total == usize::MAX {
+                break;
+            }
+        }
+        total
+    }
+
+    /// Like `fuel_for_module`, but for masses too big for a usize, returning an
+    /// error if the total doesn't fit in a u128.
+    pub fn wide_fuel_for_module(&self, mass: u128) -> Result<u128, FuelOverflow> {
+        let mut total: u128 = 0;
+        let mut fuel = self.wide_fuel_from_mass(mass);
+        while fuel > 0 {
+            total = total.checked_add(fuel).ok_or(FuelOverflow { mass })?;
+            fuel = self.wide_fuel_from_mass(fuel);
+        }
+        Ok(total)
+    }
+
+    /// The fuel stages for each module.
+    pub fn breakdown(&self, masses: &[usize]) -> Vec<ModuleFuel> {
+        masses
+            .iter()
+            .map(|&mass| ModuleFuel {
+                mass,
+                stages: self.fuel_chain(mass).collect(),
+            })
+            .collect()
+    }
+
+    /// The total fuel for all the modules, both without and with accounting
+    /// for fuel.
+    pub fn totals(&self, masses: &[usize]) -> (usize, usize) {
+        masses.iter().fold((0, 0), |(fuel, total), &mass| {
+            let mut chain = self.fuel_chain(mass);
+            let first = chain.next().unwrap_or(0);
+            (fuel + first, total + first + chain.sum::<usize>())
+        })
+    }
+
+    /// Like `totals`, but returns an error for the first module that makes
+    /// either total overflow.
+    pub fn checked_totals(&self, masses: &[usize]) -> Result<(usize, usize), FuelOverflow> {
+        masses
+            .iter()
+            .try_fold((0usize, 0usize), |(fuel, total), &mass| {
+                let overflow = FuelOverflow { mass: mass as u128 };
+                let module_total = self.checked_fuel_for_module(mass)?;
+                Ok((
+                    fuel.checked_add(self.fuel_from_mass(mass))
+                        .ok_or(overflow)?,
+                    total.checked_add(module_total).ok_or(overflow)?,
+                ))
+            })
+    }
+
+    /// Like `totals`, but stops at `usize::MAX` instead of overflowing.
+    pub fn saturating_totals(&self, masses: &[usize]) -> (usize, usize) {
+        masses
+            .iter()
+            .fold((0usize, 0usize), |(fuel, total), &mass| {
+                (
+                    fuel.saturating_add(self.fuel_from_mass(mass)),
+                    total.saturating_add(self.saturating_fuel_for_module(mass)),
+                )
+            })
+    }
+}
+
+/// The fuel for a module's mass doesn't fit in the integer type used.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FuelOverflow {
+    pub mass: u128,
+}
+
+impl fmt::Display for FuelOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fuel overflowed for module of mass {}", self.mass)
+    }
+}
+
+impl std::error::Error for FuelOverflow {}
+
+/// Exact fuel totals for any number of modules of any u128 mass.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FuelTotals {
+    /// The fuel for the modules alone
+    pub fuel: BigUint,
+    /// The fuel for the modules and all of their fuel
+    pub total: BigUint,
+}
+
+impl FuelTotals {
+    pub fn new() -> FuelTotals {
+        FuelTotals::default()
+    }
+    /// Add a module's fuel to the totals. Only fails if the module's own fuel
+    /// total doesn't fit in a u128.
+    pub fn add(&mut self, model: &FuelModel, mass: u128) -> Result<(), FuelOverflow> {
+        let total = model.wide_fuel_for_module(mass)?;
+        self.fuel += model.wide_fuel_from_mass(mass);
+        self.total += total;
+        Ok(())
+    }
+}
+
+/// The stages of fuel needed for a mass, see `FuelModel::fuel_chain`.
+#[derive(Clone, Debug)]
+pub struct FuelChain {
+    model: FuelModel,
+    mass: usize,
+}
+
+impl Iterator for FuelChain {
+    type Item = usize;
+    fn next(&mut self) -> Option<usize> {
+        let fuel = self.model.fuel_from_mass(self.mass);
+        self.mass = fuel;
+        if fuel > 0 {
+            Some(fuel)
+        } else {
+            None
+        }
+    }
+}
+
+/// The fuel needed for one module, stage by stage.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ModuleFuel {
+    pub mass: usize,
+    pub stages: Vec<usize>,
+}
+
+impl ModuleFuel {
+    /// The fuel for the module alone
+    pub fn fuel(&self) -> usize {
+        self.stages.first().copied().unwrap_or(0)
+    }
+    /// The fuel for the module and all of its fuel
+    pub fn total(&self) -> usize {
+        self.stages.iter().sum()
+    }
+}
+
+/// Gets the amount of fuel required for a module based on it's mass.
+pub fn fuel_from_mass(mass: usize) -> usize {
+    FuelModel::STANDARD.fuel_from_mass(mass)
+}
+
+/// Gets the amount of fuel required for a module *accounting for fuel*
+pub fn fuel_for_module(mass: usize) -> usize {
+    FuelModel::STANDARD.fuel_for_module(mass)
+}
+
+/// Day 1: the fuel for each module, then for each module and its fuel.
+pub struct Day1;
+
+impl Day1 {
+    fn totals(masses: &[u128]) -> Result<FuelTotals, String> {
+        let mut totals = FuelTotals::new();
+        for &mass in masses {
+            totals
+                .add(&FuelModel::STANDARD, mass)
+                .map_err(|overflow| overflow.to_string())?;
+        }
+        Ok(totals)
+    }
+}
+
+impl Solution for Day1 {
+    type Input = Vec<u128>;
+    fn parse(input: &str) -> Result<Vec<u128>, String> {
+        input::records(input.as_bytes())
+            .map(|record| record.map(|record| record.value))
+            .collect::<Result<_, _>>()
+            .map_err(|error| error.to_string())
+    }
+    fn part1(masses: &Vec<u128>) -> Answer {
+        Day1::totals(masses).map(|totals| totals.fuel.to_string())
+    }
+    fn part2(masses: &Vec<u128>) -> Answer {
+        Day1::totals(masses).map(|totals| totals.total.to_string())
+    }
+}
@@ -0,0 +1,148 @@
+// Day 2 2019
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{ParseError, Span};
+    use crate::y2019::day02::*;
+    #[test]
+    fn test_string_to_intcode() {
+        assert_eq!(string_to_intcode("1, 0,0,3,99"), vec![1, 0, 0, 3, 99]);
+        assert_eq!(
+            string_to_intcode("1,9,10,3,2,3,11,0,99,30,40,50"),
+            vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50]
+        );
+    }
+    #[test]
+    fn test_parse_intcode() {
+        assert_eq!(parse_intcode("1,0,0,3,99\n"), Ok(vec![1, 0, 0, 3, 99]));
+        assert_eq!(parse_intcode("1,0,,99").unwrap_err().span, Span::new(4, 5));
+        assert_eq!(
+            parse_intcode("1,-2"),
+            Err(ParseError::new(Span::new(2, 3), "expected an integer"))
+        );
+    }
+    #[test]
+    fn test_intcode_add() {
+        let program = &mut string_to_intcode("1, 1, 2, 0");
+        intcode_add(program, 1, 2, 3);
+        assert_eq!(program, &vec![3, 1, 2, 0]);
+    }
+    #[test]
+    fn test_intcode_multiply() {
+        let program = &mut string_to_intcode("2, 1, 2, 0");
+        intcode_multiply(program, 1, 2, 3);
+        assert_eq!(program, &vec![2, 1, 2, 0]);
+    }
+    #[test]
+    fn test_find_noun_verb() {
+        // Adds the values at the noun and verb into position 0, with each
+        // position past the program holding its own address
+        let mut program = string_to_intcode("1,0,0,0,99");
+        program.extend(5..120);
+        assert_eq!(intcode_run(&program, 10, 20), 30);
+        let (noun, verb) = find_noun_verb(&program, 150).unwrap();
+        assert_eq!(intcode_run(&program, noun, verb), 150);
+        assert_eq!(find_noun_verb(&program, 1000), None);
+    }
+    #[test]
+    fn test_intcode_execute() {
+        let mut program = string_to_intcode("1,1,1,4,99,5,6,0,99");
+        program = intcode_execute(&mut program).to_vec();
+        assert_eq!(program, string_to_intcode("30,1,1,4,2,5,6,0,99"));
+
+        let mut program1 = string_to_intcode("2,4,4,5,99,0");
+        program1 = intcode_execute(&mut program1).to_vec();
+        assert_eq!(program1, string_to_intcode("2,4,4,5,99,9801"));
+    }
+}
+
+use crate::log::Progress;
+use crate::parse::{integer, literal, ParseError};
+use crate::solution::{Answer, Solution};
+
+fn intcode_add(program: &mut [usize], first: usize, second: usize, third: usize) {
+    let first_index = program[first];
+    let second_index = program[second];
+    let output_index = program[third];
+    program[output_index] = program[first_index] + program[second_index];
+}
+
+fn intcode_multiply(program: &mut [usize], first: usize, second: usize, third: usize) {
+    let first_index = program[first];
+    let second_index = program[second];
+    let output_index = program[third];
+    program[output_index] = program[first_index] * program[second_index];
+}
+
+pub fn intcode_execute(program: &mut Vec<usize>) -> &Vec<usize> {
+    let mut command = 0;
+    loop {
+        match program[command] {
+            1 => intcode_add(program, command + 1, command + 2, command + 3),
+            2 => intcode_multiply(program, command + 1, command + 2, command + 3),
+            99 => break,
+            _ => panic!(
+                "Unexpected opcode {} at position {}",
+                program[command], command
+            ),
+        }
+        command += 4;
+    }
+    program
+}
+
+/// Parses a comma separated Intcode program.
+pub fn parse_intcode(string: &str) -> Result<Vec<usize>, ParseError> {
+    integer().trim().sep_by(literal(",")).parse(string)
+}
+
+/// Parses a comma separated Intcode program.
+///
+/// Panics if the program is malformed, see `parse_intcode`.
+pub fn string_to_intcode(string: &str) -> Vec<usize> {
+    parse_intcode(string).unwrap_or_else(|error| panic!("Invalid Intcode program: {}", error))
+}
+
+/// Runs a copy of `program` with its noun and verb (positions 1 and 2) set,
+/// and returns its output (position 0).
+pub fn intcode_run(program: &[usize], noun: usize, verb: usize) -> usize {
+    let mut program = program.to_vec();
+    program[1] = noun;
+    program[2] = verb;
+    intcode_execute(&mut program);
+    program[0]
+}
+
+/// Finds the noun and verb, each at most 99, that make `program` output
+/// `target`.
+pub fn find_noun_verb(program: &[usize], target: usize) -> Option<(usize, usize)> {
+    let progress = Progress::new("day 2 noun and verb", 100 * 100);
+    let found = (0..=99)
+        .flat_map(|noun| (0..=99).map(move |verb| (noun, verb)))
+        .find(|&(noun, verb)| {
+            progress.advance(1);
+            intcode_run(program, noun, verb) == target
+        });
+    progress.finish();
+    found
+}
+
+/// Day 2: restoring the gravity assist program, then finding the input that
+/// lands the moon.
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<usize>;
+    fn parse(input: &str) -> Result<Vec<usize>, String> {
+        parse_intcode(input).map_err(|error| error.to_string())
+    }
+    fn part1(program: &Vec<usize>) -> Answer {
+        Ok(intcode_run(program, 12, 2).to_string())
+    }
+    fn part2(program: &Vec<usize>) -> Answer {
+        match find_noun_verb(program, 19690720) {
+            Some((noun, verb)) => Ok((100 * noun + verb).to_string()),
+            None => Err("no noun and verb give 19690720".to_string()),
+        }
+    }
+}
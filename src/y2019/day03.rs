@@ -0,0 +1,377 @@
+// Day 3 2019
+
+use crate::geometry::Direction;
+pub use crate::geometry::{manhattan_distance, Line, Point};
+use crate::grid::SparseGrid;
+use crate::parse::{integer, keyed, literal, ParseError, Parser};
+use crate::solution::{Answer, Solution};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::Span;
+    use crate::rng::XorShift;
+    use crate::y2019::day03::*;
+    #[test]
+    fn test_string_to_path() {
+        let path = vec![
+            Pathlet::Right(8),
+            Pathlet::Up(5),
+            Pathlet::Left(5),
+            Pathlet::Down(3),
+        ];
+        assert_eq!(string_to_path("R8,U5,L5,D3"), path);
+    }
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse_path("U5,L12"),
+            Ok(vec![Pathlet::Up(5), Pathlet::Left(12)])
+        );
+        let error = parse_path("R8,X5").unwrap_err();
+        assert_eq!(error.span, Span::new(3, 4));
+        assert_eq!(parse_path("R8,").unwrap_err().span, Span::new(3, 3));
+        assert_eq!(
+            parse_wires("R8,U5\nU7\n"),
+            Ok(vec![
+                vec![Pathlet::Right(8), Pathlet::Up(5)],
+                vec![Pathlet::Up(7)]
+            ])
+        );
+    }
+    #[test]
+    fn test_pathlet_from_str() {
+        assert_eq!(Pathlet::from_str("R8"), Ok(Pathlet::Right(8)));
+        assert_eq!(Pathlet::from_str("N3"), Ok(Pathlet::Up(3)));
+        assert_eq!(Pathlet::from_str("SW12"), Ok(Pathlet::DownLeft(12)));
+        assert_eq!(Pathlet::from_str("UR1"), Ok(Pathlet::UpRight(1)));
+        assert!(Pathlet::from_str("").is_err());
+        assert!(Pathlet::from_str("X1").is_err());
+        assert!(Pathlet::from_str("R").is_err());
+        assert!(Pathlet::from_str("R1,").is_err());
+    }
+    #[test]
+    fn test_pathlet_display() {
+        assert_eq!(Pathlet::Right(8).to_string(), "R8");
+        assert_eq!(Pathlet::DownLeft(2).to_string(), "DL2");
+        let path = vec![Pathlet::Right(8), Pathlet::Up(5), Pathlet::UpLeft(5)];
+        assert_eq!(path_to_string(&path), "R8,U5,UL5");
+        assert_eq!(
+            path_to_string(&parse_path("E8,N5,NW5").unwrap()),
+            "R8,U5,UL5"
+        );
+    }
+    #[test]
+    fn test_path_round_trip() {
+        let mut rng = XorShift::new(3);
+        for _ in 0..500 {
+            let length = rng.range(1, 20);
+            let path: Vec<Pathlet> = (0..length)
+                .map(|_| {
+                    let (_, pathlet) = rng.choose(&Pathlet::KINDS).unwrap();
+                    pathlet(rng.below(1000) as usize)
+                })
+                .collect();
+            assert_eq!(parse_path(&path_to_string(&path)), Ok(path));
+        }
+    }
+    #[test]
+    fn test_diagonal_wires() {
+        let red = trace_wire(string_to_path("UR4,R2"));
+        let green = trace_wire(string_to_path("R3,U5"));
+        assert_eq!(red[0], Line::new(Point::origin(), Point::new(4, 4)));
+        let intersections = wire_intersections(&red, &green);
+        assert_eq!(intersections, vec![Point::new(3, 3)]);
+        assert_eq!(wire_length_to(&red, &Point::new(3, 3)), 3);
+        assert_eq!(wire_length_to(&green, &Point::new(3, 3)), 6);
+        assert_eq!(rasterize_wire(&red)[Point::new(3, 3)], 3);
+    }
+    #[test]
+    fn test_checked_trace_wire() {
+        let far = isize::MAX as usize;
+        assert!(checked_trace_wire(vec![Pathlet::Right(far), Pathlet::Left(far)]).is_some());
+        assert!(checked_trace_wire(vec![Pathlet::Right(far), Pathlet::Right(1)]).is_none());
+        assert!(checked_trace_wire(vec![Pathlet::DownLeft(far + 1)]).is_none());
+    }
+    #[test]
+    fn test_trace_wire() {
+        assert_eq!(
+            trace_wire(vec![
+                Pathlet::Right(2),
+                Pathlet::Up(2),
+                Pathlet::Left(2),
+                Pathlet::Down(2),
+            ]),
+            vec![
+                Line::new(Point::new(0, 0), Point::new(2, 0)),
+                Line::new(Point::new(2, 0), Point::new(2, 2)),
+                Line::new(Point::new(2, 2), Point::new(0, 2)),
+                Line::new(Point::new(0, 2), Point::new(0, 0)),
+            ]
+        );
+    }
+    #[test]
+    fn test_wire_intersections() {
+        let red = trace_wire(string_to_path("R8,U5,L5,D3"));
+        let green = trace_wire(string_to_path("U7,R6,D4,L4"));
+        let intersections = wire_intersections(&red, &green);
+        assert_eq!(intersections, vec![Point::new(6, 5), Point::new(3, 3)]);
+    }
+    #[test]
+    fn test_rasterize_wire() {
+        let red = rasterize_wire(&trace_wire(string_to_path("R8,U5,L5,D3")));
+        let green = rasterize_wire(&trace_wire(string_to_path("U7,R6,D4,L4")));
+        assert_eq!(red.len(), 8 + 5 + 5 + 3 + 1);
+        assert_eq!(red[Point::new(8, 2)], 10);
+        let mut crossings: Vec<(Point, usize)> = red
+            .iter()
+            .filter(|(point, _)| *point != Point::origin())
+            .filter_map(|(point, steps)| green.get(point).map(|other| (point, steps + other)))
+            .collect();
+        crossings.sort();
+        assert_eq!(
+            crossings,
+            vec![(Point::new(3, 3), 40), (Point::new(6, 5), 30)]
+        );
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Pathlet {
+    Right(usize),
+    Up(usize),
+    Left(usize),
+    Down(usize),
+    UpRight(usize),
+    UpLeft(usize),
+    DownLeft(usize),
+    DownRight(usize),
+}
+
+/// How a kind of pathlet is written, and how to make one
+type PathletKind = (&'static str, fn(usize) -> Pathlet);
+
+impl Pathlet {
+    /// Every kind of pathlet, with the direction it's written as in paths
+    const KINDS: [PathletKind; 8] = [
+        ("R", Pathlet::Right),
+        ("U", Pathlet::Up),
+        ("L", Pathlet::Left),
+        ("D", Pathlet::Down),
+        ("UR", Pathlet::UpRight),
+        ("UL", Pathlet::UpLeft),
+        ("DL", Pathlet::DownLeft),
+        ("DR", Pathlet::DownRight),
+    ];
+    /// Compass directions which can be used instead
+    const COMPASS: [PathletKind; 8] = [
+        ("E", Pathlet::Right),
+        ("N", Pathlet::Up),
+        ("W", Pathlet::Left),
+        ("S", Pathlet::Down),
+        ("NE", Pathlet::UpRight),
+        ("NW", Pathlet::UpLeft),
+        ("SW", Pathlet::DownLeft),
+        ("SE", Pathlet::DownRight),
+    ];
+    /// The number of steps the pathlet takes
+    pub fn distance(&self) -> usize {
+        match *self {
+            Pathlet::Right(distance)
+            | Pathlet::Up(distance)
+            | Pathlet::Left(distance)
+            | Pathlet::Down(distance)
+            | Pathlet::UpRight(distance)
+            | Pathlet::UpLeft(distance)
+            | Pathlet::DownLeft(distance)
+            | Pathlet::DownRight(distance) => distance,
+        }
+    }
+    /// The direction the pathlet is written as in paths, e.g. `R` or `UL`
+    pub fn direction(&self) -> &'static str {
+        let kind = Pathlet::KINDS
+            .iter()
+            .find(|(_, pathlet)| pathlet(self.distance()) == *self)
+            .unwrap();
+        kind.0
+    }
+    /// Where one step of the pathlet goes from the origin. Wires are y-up, so
+    /// `U` increases y, unlike in `geometry`.
+    pub fn step(&self) -> Point {
+        let (first, second) = match self {
+            Pathlet::Right(_) => (Direction::Right, None),
+            Pathlet::Up(_) => (Direction::Up, None),
+            Pathlet::Left(_) => (Direction::Left, None),
+            Pathlet::Down(_) => (Direction::Down, None),
+            Pathlet::UpRight(_) => (Direction::Up, Some(Direction::Right)),
+            Pathlet::UpLeft(_) => (Direction::Up, Some(Direction::Left)),
+            Pathlet::DownLeft(_) => (Direction::Down, Some(Direction::Left)),
+            Pathlet::DownRight(_) => (Direction::Down, Some(Direction::Right)),
+        };
+        let step = first.offset() + second.map_or(Point::origin(), Direction::offset);
+        Point::new(step.x(), -step.y())
+    }
+}
+
+impl fmt::Display for Pathlet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.direction(), self.distance())
+    }
+}
+
+impl FromStr for Pathlet {
+    type Err = ParseError;
+    /// Parses a single pathlet, like `R8`, `NE3`, or `DL2`
+    fn from_str(string: &str) -> Result<Pathlet, ParseError> {
+        pathlet_parser().parse(string)
+    }
+}
+
+/// Converts a vector of Pathlets to the lines of the wire, starting at the
+/// origin, or `None` if the wire goes further than an `isize` reaches
+pub fn checked_trace_wire(path: Vec<Pathlet>) -> Option<Vec<Line>> {
+    let mut wire = vec![];
+    let mut location: Point = Point::origin();
+    for pathlet in path {
+        let distance = isize::try_from(pathlet.distance()).ok()?;
+        let step = pathlet.step();
+        let next = Point::new(
+            location.x().checked_add(step.x().checked_mul(distance)?)?,
+            location.y().checked_add(step.y().checked_mul(distance)?)?,
+        );
+        wire.push(Line::new(location, next));
+        location = next;
+    }
+    Some(wire)
+}
+
+/// Converts a vector of Pathlets to the lines of the wire, starting at the origin
+///
+/// Panics if the wire goes too far, see `checked_trace_wire`.
+pub fn trace_wire(path: Vec<Pathlet>) -> Vec<Line> {
+    checked_trace_wire(path).expect("the wire goes further than an isize reaches")
+}
+
+pub fn wire_intersections(red: &[Line], green: &[Line]) -> Vec<Point> {
+    let mut intersections = vec![];
+    for red_line in red {
+        for green_line in green {
+            if let Some(x) = red_line.find_intersection(green_line) {
+                intersections.push(x);
+            }
+        }
+    }
+    // The intersection at (0, 0) doesn't count
+    intersections.split_off(1)
+}
+
+/// Distance from the origin to a point along a wire. Point must be in the wire.
+pub fn wire_length_to(wire: &[Line], point: &Point) -> usize {
+    let mut distance = 0;
+    for line in wire {
+        if line.contains_point(point) {
+            // The line starts at p1, so the distance for this segment is the
+            // distance between start of the line and the point.
+            distance += Line::new(line.p1(), *point).length() as usize;
+            break;
+        }
+        distance += line.length() as usize;
+    }
+    distance
+}
+
+/// Rasterizes a wire into a grid of every point it passes through, each set to
+/// the number of steps along the wire it takes to first reach that point.
+pub fn rasterize_wire(wire: &[Line]) -> SparseGrid<usize> {
+    let mut grid = SparseGrid::new(0);
+    let mut steps = 0;
+    grid.insert(Point::origin(), steps);
+    for line in wire {
+        let direction = line.p2() - line.p1();
+        let step = Point::new(direction.x().signum(), direction.y().signum());
+        let mut location = line.p1();
+        while location != line.p2() {
+            location += step;
+            steps += 1;
+            grid.entry_or_insert_with(location, || steps);
+        }
+    }
+    grid
+}
+
+/// Parses a single pathlet, written with either the `R`/`U`/`L`/`D` or the
+/// compass directions, e.g. `R8`, `UL2`, or `NE3`
+pub fn pathlet_parser<'p>() -> Parser<'p, Pathlet> {
+    let kinds = Pathlet::KINDS.iter().chain(Pathlet::COMPASS.iter());
+    keyed(kinds.cloned().collect())
+        .and(integer())
+        .map(|(pathlet, distance)| pathlet(distance))
+}
+
+/// Parses a comma separated list of Pathlets, like `R8,U5,L5,D3`
+pub fn path_parser<'p>() -> Parser<'p, Vec<Pathlet>> {
+    pathlet_parser().sep_by(literal(","))
+}
+
+/// Parses a comma separated list of Pathlets
+pub fn parse_path(string: &str) -> Result<Vec<Pathlet>, ParseError> {
+    path_parser().parse(string)
+}
+
+/// Parses one path per line
+pub fn parse_wires(string: &str) -> Result<Vec<Vec<Pathlet>>, ParseError> {
+    path_parser().lines().parse(string)
+}
+
+/// Writes a path the way it's parsed, like `R8,U5,L5,D3`
+pub fn path_to_string(path: &[Pathlet]) -> String {
+    let pathlets: Vec<String> = path.iter().map(Pathlet::to_string).collect();
+    pathlets.join(",")
+}
+
+/// Converts a comma seperated list of Pathlet strs to a vector of Pathlets
+///
+/// Panics if the path is malformed, see `parse_path`.
+pub fn string_to_path(string: &str) -> Vec<Pathlet> {
+    parse_path(string).unwrap_or_else(|error| panic!("Invalid path: {}", error))
+}
+
+/// Day 3: the crossing closest to the port, then the one the wires reach
+/// soonest.
+pub struct Day3;
+
+impl Solution for Day3 {
+    /// The two wires
+    type Input = (Vec<Line>, Vec<Line>);
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        let wires = parse_wires(input).map_err(|error| error.to_string())?;
+        if wires.len() != 2 {
+            return Err(format!("expected 2 wires, found {}", wires.len()));
+        }
+        let mut wires = wires
+            .into_iter()
+            .map(checked_trace_wire)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| "a wire goes too far from the port".to_string())?
+            .into_iter();
+        Ok((wires.next().unwrap(), wires.next().unwrap()))
+    }
+    fn part1((red, green): &Self::Input) -> Answer {
+        wire_intersections(red, green)
+            .iter()
+            .map(Point::distance)
+            .min()
+            .map(|distance| distance.to_string())
+            .ok_or_else(|| "the wires never cross".to_string())
+    }
+    fn part2((red, green): &Self::Input) -> Answer {
+        wire_intersections(red, green)
+            .iter()
+            .map(|x| wire_length_to(red, x) + wire_length_to(green, x))
+            .min()
+            .map(|length| length.to_string())
+            .ok_or_else(|| "the wires never cross".to_string())
+    }
+}
@@ -0,0 +1,772 @@
+// Day 4 2019
+
+use crate::combinatorics::stars_and_bars;
+use crate::digits::{self, digits};
+use crate::log::Progress;
+use crate::parse::{integer, literal, ParseError};
+use crate::scan::{inclusive_bounds, scan};
+use crate::solution::{Answer, Solution};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::{Bound, RangeBounds};
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::XorShift;
+    use crate::y2019::day04::*;
+    #[test]
+    fn test_count_ascending() {
+        assert_eq!(count_ascending(3, 2), 4);
+        assert_eq!(count_ascending(3, 3), 10);
+        assert_eq!(count_ascending(3, 4), 20);
+        // Ascending numbers can't have zeros after the first digit, so every
+        // n digit one uses 1..=9
+        for n in 1..=8 {
+            let lower = 10u64.pow(n as u32 - 1);
+            assert_eq!(
+                NonDecreasing.count(lower..lower * 10),
+                count_ascending(n, 9) as u64
+            );
+        }
+    }
+    #[test]
+    fn test_count_ascending_with_pairs_between() {
+        assert_eq!(count_ascending_with_pairs_between(6, 165432, 707912), 1716);
+    }
+    #[test]
+    fn test_count_matching_puzzle() {
+        let range = 165432..=707912;
+        assert_eq!(
+            count_matching(range.clone(), &(NonDecreasing, AtLeastOnePair)),
+            1716
+        );
+        assert_eq!(count_matching(range, &(NonDecreasing, ExactlyTwoRun)), 1163);
+    }
+    #[test]
+    fn test_count_matching_bounds() {
+        let rule = (NonDecreasing, AtLeastOnePair);
+        // 11, 22, ..., 99
+        assert_eq!(count_matching(0..100, &rule), 9);
+        assert_eq!(count_matching(11..=99, &rule), 9);
+        assert_eq!(count_matching(11..99, &rule), 8);
+        assert_eq!(
+            count_matching((Bound::Excluded(11), Bound::Included(99)), &rule),
+            8
+        );
+        assert_eq!(count_matching(..=99, &rule), 9);
+        assert_eq!(count_matching(50..50, &rule), 0);
+        assert_eq!(count_matching(u64::MAX.., &rule), 0);
+        // The length can vary within the range: 99, the 3 digit numbers, then
+        // 1111 through 1119 and 1122
+        assert_eq!(
+            count_matching(99..=1122, &rule),
+            1 + count_matching(100..1000, &rule) + 10
+        );
+        assert_eq!(count_matching(0..10, &NonDecreasing), 10);
+    }
+    #[test]
+    fn test_count_matching_agrees_with_brute() {
+        let mut rng = XorShift::new(4);
+        let exactly_two = |number: usize| {
+            let digits: Vec<u64> = digits(number as u64, 10).collect();
+            let mut runs = vec![1];
+            for i in 1..digits.len() {
+                if digits[i] == digits[i - 1] {
+                    *runs.last_mut().unwrap() += 1;
+                } else {
+                    runs.push(1);
+                }
+            }
+            runs.contains(&2)
+        };
+        for _ in 0..30 {
+            let magnitude = 10u64.pow(rng.range(1, 7) as u32);
+            let min = rng.below(magnitude);
+            let max = min + rng.below(5000);
+            assert_eq!(
+                count_matching(min..max, &(NonDecreasing, AtLeastOnePair)),
+                brute(min as usize, max as usize) as u64,
+                "{}..{}",
+                min,
+                max
+            );
+            let part2 = (min..max)
+                .filter(|&n| has_increasing_digits(n as usize) && exactly_two(n as usize))
+                .count();
+            assert_eq!(
+                count_matching(min..max, &(NonDecreasing, ExactlyTwoRun)),
+                part2 as u64
+            );
+        }
+    }
+    #[test]
+    fn test_password_rules() {
+        assert!(NonDecreasing.check(111123));
+        assert!(!NonDecreasing.check(223450));
+        assert!(AtLeastOnePair.check(122345) && !AtLeastOnePair.check(123789));
+        assert!(ExactlyTwoRun.check(111122) && !ExactlyTwoRun.check(123444));
+        assert!(MaxRun(3).check(111223) && !MaxRun(3).check(111123));
+        assert!(DigitSum(6).check(123) && DigitSum(0).check(0) && !DigitSum(6).check(124));
+        assert!(MaxRepeats(2).check(121323) && !MaxRepeats(2).check(121312));
+    }
+    #[test]
+    fn test_password_rule_combinators() {
+        let part1 = NonDecreasing.and(AtLeastOnePair);
+        assert!(part1.check(112233) && !part1.check(223450) && !part1.check(123789));
+        let pair_or_sum = AtLeastOnePair.or(DigitSum(3));
+        assert!(pair_or_sum.check(1223) && pair_or_sum.check(102) && !pair_or_sum.check(104));
+        let no_pair = AtLeastOnePair.not();
+        assert!(no_pair.check(1212) && !no_pair.check(1122) && no_pair.check(0));
+        // Part 2 is part 1 without the numbers whose only runs are too long
+        let range = 165432..=707912;
+        let runs_too_long = part1.and(ExactlyTwoRun.not());
+        assert_eq!(
+            part1.count(range.clone()) - runs_too_long.count(range.clone()),
+            NonDecreasing.and(ExactlyTwoRun).count(range)
+        );
+    }
+    #[test]
+    fn test_password_rule_matching() {
+        let part1 = NonDecreasing.and(AtLeastOnePair);
+        let first: Vec<u64> = part1.matching(165432..).take(4).collect();
+        assert_eq!(first, vec![166666, 166667, 166668, 166669]);
+        assert_eq!(
+            DigitSum(2).matching(..).take(10).collect::<Vec<_>>(),
+            vec![2, 11, 20, 101, 110, 200, 1001, 1010, 1100, 2000]
+        );
+        assert_eq!(
+            DigitSum(2).matching(..).count() as u64,
+            DigitSum(2).count(..)
+        );
+        assert_eq!(MaxRun(0).matching(..).next(), None);
+        assert_eq!(AtLeastOnePair.not().matching(0..=3).count(), 4);
+        let mut rng = XorShift::new(37);
+        let rule = AtLeastOnePair.or(DigitSum(10)).and(MaxRepeats(2));
+        for _ in 0..20 {
+            let min = rng.below(100_000);
+            let max = min + rng.below(3000);
+            let expected: Vec<u64> = (min..max).filter(|&n| rule.check(n)).collect();
+            assert_eq!(rule.matching(min..max).collect::<Vec<_>>(), expected);
+            assert_eq!(rule.count(min..max), expected.len() as u64);
+        }
+    }
+    #[test]
+    fn test_ends() {
+        assert!(within_range(1, 3, 2) && !within_range(1, 3, 1) && !within_range(1, 3, 3));
+        assert!(Ends::Inclusive.contains(1, 3, 1) && Ends::Inclusive.contains(1, 3, 3));
+        let part1 = NonDecreasing.and(AtLeastOnePair);
+        // Every 6 digit ascending number, less the 84 with no repeats. 111111
+        // and 999999 are the ends.
+        let every = count_ascending(6, 9) as u64 - 84;
+        assert_eq!(part1.count(Ends::Inclusive.bounds(111111, 999999)), every);
+        assert_eq!(
+            part1.count(Ends::Exclusive.bounds(111111, 999999)),
+            every - 2
+        );
+        assert_eq!(part1.count(Ends::Exclusive.bounds(5, 6)), 0);
+    }
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("165432-707912\n"), Ok((165432, 707912)));
+        assert!(parse_range("165432 707912").is_err());
+        assert!(parse_range("707912-165432").is_err());
+    }
+    #[test]
+    fn test_brute_count() {
+        let part2 = NonDecreasing.and(ExactlyTwoRun);
+        for &ends in &[Ends::Inclusive, Ends::Exclusive] {
+            let bounds = ends.bounds(112233, 123444);
+            assert_eq!(brute_count(bounds, &part2), part2.count(bounds));
+        }
+        assert_eq!(brute_count(10..10, &NonDecreasing), 0);
+    }
+    #[test]
+    fn test_brute() {
+        assert_eq!(brute(111100, 111200), 45);
+        // Checking every number takes forever, so skip the numbers whose
+        // digits decrease and split the rest across threads
+        let part1 = |n: u64| has_double(n as usize) && has_increasing_digits(n as usize);
+        let scanner = scan(165432..707912, part1).skip_with(|n| digits::next_non_decreasing(n, 10));
+        assert_eq!(scanner.count(), 1716);
+        assert_eq!(scanner.matches()[..3], [166666, 166667, 166668]);
+    }
+}
+
+/// Checks whether a number has increasing digits.
+pub fn has_increasing_digits(number: usize) -> bool {
+    let number = number as u64;
+    digits(number, 10)
+        .zip(digits(number, 10).skip(1))
+        .all(|(d, next)| d <= next)
+}
+
+/// Checks a number is within the interval (lower, upper), not including the
+/// ends. The puzzle's range includes them, see `Ends`.
+pub fn within_range(lower: usize, upper: usize, number: usize) -> bool {
+    Ends::Exclusive.contains(lower as u64, upper as u64, number as u64)
+}
+
+/// Whether a range between two numbers includes the numbers themselves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ends {
+    /// Both ends are in the range, like the puzzle's range
+    Inclusive,
+    /// Neither end is in the range, like `within_range`
+    Exclusive,
+}
+
+impl Ends {
+    /// The range from `lower` to `upper` with these ends, for `count_matching`
+    /// and friends
+    pub fn bounds(self, lower: u64, upper: u64) -> (Bound<u64>, Bound<u64>) {
+        match self {
+            Ends::Inclusive => (Bound::Included(lower), Bound::Included(upper)),
+            Ends::Exclusive => (Bound::Excluded(lower), Bound::Excluded(upper)),
+        }
+    }
+    /// Whether `number` is between `lower` and `upper` with these ends
+    pub fn contains(self, lower: u64, upper: u64, number: u64) -> bool {
+        self.bounds(lower, upper).contains(&number)
+    }
+}
+
+/// Parses the puzzle input, a range like `165432-707912`, into its ends.
+pub fn parse_range(string: &str) -> Result<(u64, u64), ParseError> {
+    integer()
+        .skip(literal("-"))
+        .and(integer())
+        .try_map(|(lower, upper)| {
+            if lower <= upper {
+                Ok((lower, upper))
+            } else {
+                Err(format!("{} is after {}", lower, upper))
+            }
+        })
+        .trim()
+        .parse(string)
+}
+
+/// Checks whether a number has an adjacent repeated digit.
+pub fn has_double(number: usize) -> bool {
+    let number = number as u64;
+    digits(number, 10)
+        .zip(digits(number, 10).skip(1))
+        .any(|(d, next)| d == next)
+}
+
+/// Calculates the number of `n` length numbers of `d` digits where the digits
+/// are ascending.
+/// - E.g. a 3 digit number with digits 1..=4: `n` = 3, `d` = 4
+/// - E.g. a 3 digit number with digits 5..=9: `n` = 3, `d` = 5
+pub fn count_ascending(n: usize, d: usize) -> usize {
+    if d == 0 {
+        panic!("d must be a positive integer, not {}", d);
+    }
+    if n == 0 {
+        // There are no 0 length numbers
+        return 0;
+    }
+    // Ascending digits are a choice of n digits with repetition, in order
+    stars_and_bars(n as u64, d as u64).expect("too many ascending numbers to count") as usize
+}
+
+/// Calculates the number of `n` length numbers between `min` and `max`
+/// (inclusive) that have at least one pair of repeated digits and are
+/// ascending.
+pub fn count_ascending_with_pairs_between(n: usize, min: usize, max: usize) -> usize {
+    if n == 0 {
+        return 0;
+    }
+    // Only count the numbers that are actually n digits long
+    let shortest = 10u64.pow(n as u32 - 1);
+    let longest = 10u64.saturating_pow(n as u32) - 1;
+    let lower = std::cmp::max(min as u64, shortest);
+    let upper = std::cmp::min(max as u64, longest);
+    crate::log!(
+        Debug,
+        "counting {} digit numbers in {}..={}",
+        n,
+        lower,
+        upper
+    );
+    count_matching(lower..=upper, &(NonDecreasing, AtLeastOnePair)) as usize
+}
+
+/// A rule about the digits of a number, checked one digit at a time from the
+/// most significant digit, so that numbers following it can be counted without
+/// checking each one. Tuples of rules are followed when all their rules are.
+pub trait DigitRule {
+    /// What the rule needs to remember about the digits so far
+    type State: Clone + Eq + Hash;
+    /// The state before any digits
+    fn start(&self) -> Self::State;
+    /// The state after another digit, or `None` if no number starting with
+    /// these digits can follow the rule
+    fn step(&self, state: &Self::State, digit: u8) -> Option<Self::State>;
+    /// Whether a number with exactly these digits follows the rule
+    fn accept(&self, state: &Self::State) -> bool;
+}
+
+/// Each digit is at least as large as the one before it.
+#[derive(Clone, Copy, Debug)]
+pub struct NonDecreasing;
+
+impl DigitRule for NonDecreasing {
+    /// The previous digit
+    type State = u8;
+    fn start(&self) -> u8 {
+        0
+    }
+    fn step(&self, previous: &u8, digit: u8) -> Option<u8> {
+        if digit >= *previous {
+            Some(digit)
+        } else {
+            None
+        }
+    }
+    fn accept(&self, _: &u8) -> bool {
+        true
+    }
+}
+
+/// Some digit is immediately repeated.
+#[derive(Clone, Copy, Debug)]
+pub struct AtLeastOnePair;
+
+impl DigitRule for AtLeastOnePair {
+    /// The previous digit, and whether there has been a pair
+    type State = (Option<u8>, bool);
+    fn start(&self) -> Self::State {
+        (None, false)
+    }
+    fn step(&self, &(previous, paired): &Self::State, digit: u8) -> Option<Self::State> {
+        Some((Some(digit), paired || previous == Some(digit)))
+    }
+    fn accept(&self, &(_, paired): &Self::State) -> bool {
+        paired
+    }
+}
+
+/// Some digit is repeated exactly twice in a row, not as part of a longer run.
+#[derive(Clone, Copy, Debug)]
+pub struct ExactlyTwoRun;
+
+impl DigitRule for ExactlyTwoRun {
+    /// The previous digit, how many times it has been repeated, and whether
+    /// there has been a run of exactly two
+    type State = (Option<u8>, u8, bool);
+    fn start(&self) -> Self::State {
+        (None, 0, false)
+    }
+    fn step(&self, &(previous, run, found): &Self::State, digit: u8) -> Option<Self::State> {
+        if previous == Some(digit) {
+            // Cap the run at 3, since longer runs are no different
+            Some((previous, std::cmp::min(run + 1, 3), found))
+        } else {
+            Some((Some(digit), 1, found || run == 2))
+        }
+    }
+    fn accept(&self, &(_, run, found): &Self::State) -> bool {
+        found || run == 2
+    }
+}
+
+/// Both rules are followed, see `PasswordRule::and`.
+impl<A: DigitRule, B: DigitRule> DigitRule for (A, B) {
+    type State = (A::State, B::State);
+    fn start(&self) -> Self::State {
+        (self.0.start(), self.1.start())
+    }
+    fn step(&self, state: &Self::State, digit: u8) -> Option<Self::State> {
+        Some((self.0.step(&state.0, digit)?, self.1.step(&state.1, digit)?))
+    }
+    fn accept(&self, state: &Self::State) -> bool {
+        self.0.accept(&state.0) && self.1.accept(&state.1)
+    }
+}
+
+impl<A: DigitRule, B: DigitRule, C: DigitRule> DigitRule for (A, B, C) {
+    type State = (A::State, B::State, C::State);
+    fn start(&self) -> Self::State {
+        (self.0.start(), self.1.start(), self.2.start())
+    }
+    fn step(&self, state: &Self::State, digit: u8) -> Option<Self::State> {
+        Some((
+            self.0.step(&state.0, digit)?,
+            self.1.step(&state.1, digit)?,
+            self.2.step(&state.2, digit)?,
+        ))
+    }
+    fn accept(&self, state: &Self::State) -> bool {
+        self.0.accept(&state.0) && self.1.accept(&state.1) && self.2.accept(&state.2)
+    }
+}
+
+/// No digit is repeated more than this many times in a row.
+#[derive(Clone, Copy, Debug)]
+pub struct MaxRun(pub u8);
+
+impl DigitRule for MaxRun {
+    /// The previous digit and how many times it has been repeated
+    type State = (Option<u8>, u8);
+    fn start(&self) -> Self::State {
+        (None, 0)
+    }
+    fn step(&self, &(previous, run): &Self::State, digit: u8) -> Option<Self::State> {
+        let run = if previous == Some(digit) { run + 1 } else { 1 };
+        if run > self.0 {
+            None
+        } else {
+            Some((Some(digit), run))
+        }
+    }
+    fn accept(&self, _: &Self::State) -> bool {
+        true
+    }
+}
+
+/// The digits add up to exactly this.
+#[derive(Clone, Copy, Debug)]
+pub struct DigitSum(pub u64);
+
+impl DigitRule for DigitSum {
+    /// The sum of the digits so far
+    type State = u64;
+    fn start(&self) -> u64 {
+        0
+    }
+    fn step(&self, sum: &u64, digit: u8) -> Option<u64> {
+        let sum = sum + digit as u64;
+        if sum > self.0 {
+            None
+        } else {
+            Some(sum)
+        }
+    }
+    fn accept(&self, sum: &u64) -> bool {
+        *sum == self.0
+    }
+}
+
+/// No digit appears more than this many times anywhere in the number.
+#[derive(Clone, Copy, Debug)]
+pub struct MaxRepeats(pub u8);
+
+impl DigitRule for MaxRepeats {
+    /// How many times each digit has appeared
+    type State = [u8; 10];
+    fn start(&self) -> [u8; 10] {
+        [0; 10]
+    }
+    fn step(&self, counts: &[u8; 10], digit: u8) -> Option<[u8; 10]> {
+        let mut counts = *counts;
+        counts[digit as usize] += 1;
+        if counts[digit as usize] > self.0 {
+            None
+        } else {
+            Some(counts)
+        }
+    }
+    fn accept(&self, _: &[u8; 10]) -> bool {
+        true
+    }
+}
+
+/// Either rule is followed, see `PasswordRule::or`.
+#[derive(Clone, Copy, Debug)]
+pub struct Or<A, B>(pub A, pub B);
+
+impl<A: DigitRule, B: DigitRule> DigitRule for Or<A, B> {
+    /// Each rule's state, or `None` once the rule can't be followed
+    type State = (Option<A::State>, Option<B::State>);
+    fn start(&self) -> Self::State {
+        (Some(self.0.start()), Some(self.1.start()))
+    }
+    fn step(&self, state: &Self::State, digit: u8) -> Option<Self::State> {
+        let a = state.0.as_ref().and_then(|a| self.0.step(a, digit));
+        let b = state.1.as_ref().and_then(|b| self.1.step(b, digit));
+        if a.is_none() && b.is_none() {
+            None
+        } else {
+            Some((a, b))
+        }
+    }
+    fn accept(&self, state: &Self::State) -> bool {
+        state.0.as_ref().is_some_and(|a| self.0.accept(a))
+            || state.1.as_ref().is_some_and(|b| self.1.accept(b))
+    }
+}
+
+/// The rule isn't followed, see `PasswordRule::not`.
+#[derive(Clone, Copy, Debug)]
+pub struct Not<A>(pub A);
+
+impl<A: DigitRule> DigitRule for Not<A> {
+    /// The rule's state, or `None` once it can't be followed
+    type State = Option<A::State>;
+    fn start(&self) -> Self::State {
+        Some(self.0.start())
+    }
+    fn step(&self, state: &Self::State, digit: u8) -> Option<Self::State> {
+        // Once the rule can't be followed, its opposite always is
+        Some(state.as_ref().and_then(|state| self.0.step(state, digit)))
+    }
+    fn accept(&self, state: &Self::State) -> bool {
+        state.as_ref().map_or(true, |state| !self.0.accept(state))
+    }
+}
+
+/// A rule that passwords have to follow. Every `DigitRule` is one, so rules
+/// can be checked against single numbers, combined, counted, and listed.
+pub trait PasswordRule: DigitRule + Sized {
+    /// Whether `number` follows this rule
+    fn check(&self, number: u64) -> bool {
+        let mut state = self.start();
+        for digit in digits(number, 10) {
+            state = match self.step(&state, digit as u8) {
+                Some(state) => state,
+                None => return false,
+            };
+        }
+        self.accept(&state)
+    }
+    /// A rule followed when both this and `other` are
+    fn and<R: DigitRule>(self, other: R) -> (Self, R) {
+        (self, other)
+    }
+    /// A rule followed when either this or `other` is
+    fn or<R: DigitRule>(self, other: R) -> Or<Self, R> {
+        Or(self, other)
+    }
+    /// A rule followed when this one isn't
+    fn not(self) -> Not<Self> {
+        Not(self)
+    }
+    /// The number of numbers in `range` that follow this rule
+    fn count<B: RangeBounds<u64>>(&self, range: B) -> u64 {
+        count_matching(range, self)
+    }
+    /// The numbers in `range` that follow this rule, smallest first
+    fn matching<B: RangeBounds<u64>>(&self, range: B) -> Matching<'_, Self> {
+        let (next, upper) = match inclusive_bounds(&range) {
+            Some((lower, upper)) => (Some(lower), upper),
+            None => (None, 0),
+        };
+        Matching {
+            rule: self,
+            next,
+            upper,
+            dead: HashSet::new(),
+        }
+    }
+}
+
+impl<R: DigitRule> PasswordRule for R {}
+
+/// Counts the numbers in `range` whose digits (without leading zeros) follow
+/// `rule`. The range can have any combination of inclusive, exclusive, and
+/// missing bounds.
+pub fn count_matching<B: RangeBounds<u64>, R: DigitRule>(range: B, rule: &R) -> u64 {
+    let (lower, upper) = match inclusive_bounds(&range) {
+        Some(bounds) => bounds,
+        None => return 0,
+    };
+    let below = match lower {
+        0 => 0,
+        _ => count_up_to(lower - 1, rule),
+    };
+    count_up_to(upper, rule) - below
+}
+
+/// Counts the numbers in `0..=upper` whose digits follow `rule`.
+fn count_up_to<R: DigitRule>(upper: u64, rule: &R) -> u64 {
+    let digits: Vec<u8> = digits(upper, 10).map(|d| d as u8).collect();
+    let mut memo = HashMap::new();
+    // Zero has no significant digits, so check it separately
+    let zero = rule
+        .step(&rule.start(), 0)
+        .map_or(0, |state| rule.accept(&state) as u64);
+    zero + count_digits(&digits, 0, None, true, rule, &mut memo)
+}
+
+/// Counts the ways to finish a number from position `i` of `digits`, given the
+/// rule's `state` so far (`None` if every digit so far has been a leading
+/// zero), and whether every digit so far has matched `digits`.
+fn count_digits<R: DigitRule>(
+    digits: &[u8],
+    i: usize,
+    state: Option<R::State>,
+    tight: bool,
+    rule: &R,
+    memo: &mut HashMap<(usize, R::State), u64>,
+) -> u64 {
+    if i == digits.len() {
+        return state.map_or(0, |state| rule.accept(&state) as u64);
+    }
+    // Only the counts for loose, started numbers are worth remembering: there
+    // is only one tight number, and numbers that haven't started are cheap
+    if let (false, Some(state)) = (tight, &state) {
+        if let Some(&count) = memo.get(&(i, state.clone())) {
+            return count;
+        }
+    }
+    let max = if tight { digits[i] } else { 9 };
+    let mut count = 0;
+    for digit in 0..=max {
+        let next = match &state {
+            None if digit == 0 => None,
+            None => match rule.step(&rule.start(), digit) {
+                Some(next) => Some(next),
+                None => continue,
+            },
+            Some(state) => match rule.step(state, digit) {
+                Some(next) => Some(next),
+                None => continue,
+            },
+        };
+        count += count_digits(digits, i + 1, next, tight && digit == max, rule, memo);
+    }
+    if let (false, Some(state)) = (tight, state) {
+        memo.insert((i, state), count);
+    }
+    count
+}
+
+/// The numbers in a range that follow a rule, see `PasswordRule::matching`.
+pub struct Matching<'r, R: DigitRule> {
+    rule: &'r R,
+    /// The smallest number that hasn't been looked at yet
+    next: Option<u64>,
+    upper: u64,
+    /// Positions and states that no number of the current length can be
+    /// finished from
+    dead: HashSet<(usize, usize, R::State)>,
+}
+
+impl<'r, R: DigitRule> Matching<'r, R> {
+    /// The smallest number at least `lower` with `length` digits that follows
+    /// the rule, if there is one.
+    fn first_from(&mut self, lower: u64, length: usize) -> Option<u64> {
+        let smallest = 10u64.pow(length as u32 - 1);
+        let lower = std::cmp::max(lower, smallest);
+        let digits: Vec<u8> = digits(lower, 10).map(|d| d as u8).collect();
+        if digits.len() != length {
+            return None;
+        }
+        let mut found = Vec::with_capacity(length);
+        if !self.search(&digits, self.rule.start(), true, &mut found) {
+            return None;
+        }
+        // The longest numbers can be too big, and then so is everything after
+        found.iter().try_fold(0u64, |number, &d| {
+            number.checked_mul(10)?.checked_add(d as u64)
+        })
+    }
+    /// Finds the smallest way to finish a number, digit by digit, pushing its
+    /// digits onto `found`. `tight` is whether the digits so far are those of
+    /// the lower bound, so the next digit can't be any smaller.
+    fn search(&mut self, digits: &[u8], state: R::State, tight: bool, found: &mut Vec<u8>) -> bool {
+        let i = found.len();
+        if i == digits.len() {
+            return self.rule.accept(&state);
+        }
+        let key = (digits.len(), i, state);
+        if !tight && self.dead.contains(&key) {
+            return false;
+        }
+        let state = key.2;
+        let min = if tight { digits[i] } else { 0 };
+        for digit in min..=9 {
+            if let Some(next) = self.rule.step(&state, digit) {
+                found.push(digit);
+                if self.search(digits, next, tight && digit == min, found) {
+                    return true;
+                }
+                found.pop();
+            }
+        }
+        if !tight {
+            self.dead.insert((digits.len(), i, state));
+        }
+        false
+    }
+}
+
+impl<'r, R: DigitRule> Iterator for Matching<'r, R> {
+    type Item = u64;
+    fn next(&mut self) -> Option<u64> {
+        let lower = self.next?;
+        let mut number = None;
+        if lower == 0 {
+            // Zero has no significant digits, so check it separately
+            let rule = self.rule;
+            let zero = rule.step(&rule.start(), 0);
+            if zero.is_some_and(|state| rule.accept(&state)) {
+                number = Some(0);
+            }
+        }
+        if number.is_none() {
+            let shortest = digits::count(lower, 10);
+            let longest = digits::count(self.upper, 10);
+            number = (shortest..=longest).find_map(|length| self.first_from(lower, length));
+        }
+        match number {
+            Some(number) if number <= self.upper => {
+                self.next = number.checked_add(1);
+                Some(number)
+            }
+            _ => {
+                self.next = None;
+                None
+            }
+        }
+    }
+}
+
+/// Counts the numbers in `range` that follow `rule` by checking each one, on
+/// every core.
+pub fn brute_count<B: RangeBounds<u64>, R: PasswordRule + Sync>(range: B, rule: &R) -> u64 {
+    let total =
+        inclusive_bounds(&range).map_or(0, |(lower, upper)| (upper - lower).saturating_add(1));
+    let progress = Progress::new("day 4 brute force", total);
+    let count = scan(range, |n| rule.check(n)).progress(&progress).count();
+    progress.finish();
+    count
+}
+
+/// Counts the numbers in `min..max` with increasing digits and a double by
+/// checking each one.
+pub fn brute(min: usize, max: usize) -> usize {
+    let mut count = 0;
+    for number in min..max {
+        if has_double(number) && has_increasing_digits(number) {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+/// Day 4: the passwords in the range with a double, then with a double that
+/// isn't part of a longer run.
+pub struct Day4;
+
+impl Solution for Day4 {
+    /// The ends of the range, which are both in it
+    type Input = (u64, u64);
+    fn parse(input: &str) -> Result<(u64, u64), String> {
+        parse_range(input).map_err(|error| error.to_string())
+    }
+    fn part1(&(lower, upper): &(u64, u64)) -> Answer {
+        let part1 = NonDecreasing.and(AtLeastOnePair);
+        Ok(part1
+            .count(Ends::Inclusive.bounds(lower, upper))
+            .to_string())
+    }
+    fn part2(&(lower, upper): &(u64, u64)) -> Answer {
+        let part2 = NonDecreasing.and(ExactlyTwoRun);
+        Ok(part2
+            .count(Ends::Inclusive.bounds(lower, upper))
+            .to_string())
+    }
+}
@@ -0,0 +1,20 @@
+// Advent of Code 2019
+
+use crate::solution::Day;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+// new-day: days
+
+/// Every solved day of 2019, in order.
+pub fn registry() -> Vec<Day> {
+    vec![
+        Day::of::<day01::Day1>(2019, 1, "input/day1part1"),
+        Day::of::<day02::Day2>(2019, 2, "input/day2part2"),
+        Day::of::<day03::Day3>(2019, 3, "input/day3"),
+        Day::of::<day04::Day4>(2019, 4, "input/day4"),
+        // new-day: registry
+    ]
+}
@@ -0,0 +1,20 @@
+// A test for every puzzle example in fixtures/, generated by build.rs
+
+extern crate advent_of_code;
+
+use advent_of_code::fixture::{self, Fixture};
+
+fn check(path: &str) {
+    let fixture = Fixture::load(path).unwrap_or_else(|error| panic!("{}", error));
+    if let Err(error) = fixture.check() {
+        panic!("{}: {}", path, error);
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/fixtures.rs"));
+
+#[test]
+fn every_fixture_has_a_test() {
+    let root = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures");
+    assert_eq!(fixture::paths(root).len(), FIXTURES);
+}
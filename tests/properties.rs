@@ -0,0 +1,291 @@
+// Every parser gets random and mutated input, and has to return an error
+// instead of panicking. Parsers that can be written back out have to read
+// back what they wrote.
+
+extern crate advent_of_code;
+
+use advent_of_code::bench::{Baseline, Stage};
+use advent_of_code::bigint::BigUint;
+use advent_of_code::fixture::Fixture;
+use advent_of_code::grid::Grid;
+use advent_of_code::input;
+use advent_of_code::output::Format;
+use advent_of_code::property::{assert_property, integers, mutations, text, vec_of};
+use advent_of_code::solution;
+use advent_of_code::y2019::day02::parse_intcode;
+use advent_of_code::y2019::day03::{self, parse_path, parse_wires, path_to_string, Pathlet};
+use advent_of_code::y2019::day04::parse_range;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+/// Characters that trip up parsers: digits, signs, separators, whitespace,
+/// and a couple that are more than one byte.
+const NOISE: &str = "0123456789-+,.: \t\n\r#xé→";
+
+/// Fails if `parse(input)` gives something that writes out to text that
+/// doesn't parse back to it. Panics are caught by the property runner.
+fn round_trips<T, E, P, W>(input: &str, parse: P, write: W) -> Result<(), String>
+where
+    T: PartialEq + Debug,
+    E: Debug,
+    P: Fn(&str) -> Result<T, E>,
+    W: Fn(&T) -> String,
+{
+    let parsed = match parse(input) {
+        Ok(parsed) => parsed,
+        Err(_) => return Ok(()),
+    };
+    let written = write(&parsed);
+    match parse(&written) {
+        Ok(reparsed) if reparsed == parsed => Ok(()),
+        reparsed => Err(format!(
+            "{:?} was written as {:?}, which parsed as {:?}",
+            parsed, written, reparsed
+        )),
+    }
+}
+
+#[test]
+fn every_day_parses_without_panicking() {
+    for day in solution::registry() {
+        let seeds = [
+            "12\n14\n1969\n100756\n",
+            "1,9,10,3,2,3,11,0,99,30,40,50",
+            "R8,U5,L5,D3\nU7,R6,D4,L4\n",
+            "165432-707912",
+        ];
+        let name = format!("{} day {} parses", day.year, day.day);
+        let parses = |input: &String| day.parse(input).map(|_| ()).or(Ok(()));
+        assert_property(&name, &text(NOISE, 40), parses);
+        assert_property(&name, &mutations(&seeds, NOISE), parses);
+    }
+}
+
+#[test]
+fn intcode() {
+    let alphabet = "0123456789,- \n";
+    assert_property(
+        "intcode parses",
+        &mutations(&["1,9,10,3,2,3,11,0,99,30,40,50\n"], alphabet),
+        |input: &String| round_trips(input, parse_intcode, |program| join(program, ",")),
+    );
+    assert_property(
+        "intcode round trips",
+        &vec_of(integers(0, u64::MAX), 20),
+        |program: &Vec<u64>| {
+            if program.is_empty() {
+                return Ok(());
+            }
+            let program: Vec<usize> = program.iter().map(|&n| n as usize).collect();
+            match parse_intcode(&join(&program, ",")) {
+                Ok(parsed) if parsed == program => Ok(()),
+                parsed => Err(format!("parsed as {:?}", parsed)),
+            }
+        },
+    );
+}
+
+#[test]
+fn paths() {
+    let alphabet = "RULDNESW0123456789,\n ";
+    let seeds = ["R8,U5,L5,D3\nU7,R6,D4,L4", "NE3,SW10,UL2,DR0"];
+    assert_property(
+        "paths parse",
+        &mutations(&seeds, alphabet),
+        |input: &String| {
+            round_trips(input, parse_path, |path| path_to_string(path))?;
+            round_trips(input, Pathlet::from_str, Pathlet::to_string)?;
+            round_trips(input, parse_wires, |wires| {
+                let lines: Vec<String> = wires.iter().map(|path| path_to_string(path)).collect();
+                lines.join("\n")
+            })
+        },
+    );
+    assert_property("paths parse", &text(NOISE, 30), |input: &String| {
+        round_trips(input, parse_path, |path| path_to_string(path))
+    });
+    // Any path written out reads back the same
+    assert_property(
+        "paths round trip",
+        &vec_of((integers(0, 7), integers(0, 1 << 40)), 10),
+        |pathlets: &Vec<(u64, u64)>| {
+            if pathlets.is_empty() {
+                return Ok(());
+            }
+            let kinds = ["R", "U", "L", "D", "UR", "UL", "DL", "DR"];
+            let written: Vec<String> = pathlets
+                .iter()
+                .map(|&(kind, distance)| format!("{}{}", kinds[kind as usize], distance))
+                .collect();
+            let written = written.join(",");
+            let path = parse_path(&written).map_err(|error| error.to_string())?;
+            if path_to_string(&path) == written {
+                Ok(())
+            } else {
+                Err(format!("{:?} read back as {:?}", written, path))
+            }
+        },
+    );
+    // Tracing a parsed wire only fails if it goes too far, however far that is
+    assert_property(
+        "wires trace",
+        &mutations(
+            &["R9223372036854775807,L1", "DL4611686018427387904,U1"],
+            alphabet,
+        ),
+        |input: &String| {
+            for wire in parse_wires(input).unwrap_or_default() {
+                let near = stays_near(&wire);
+                let traced = day03::checked_trace_wire(wire.clone());
+                if traced.is_some() != near {
+                    return Err(format!("{:?} traced as {:?}", wire, traced));
+                }
+            }
+            Ok(())
+        },
+    );
+    // Wires that go past the edge of the grid are errors, and the rest aren't
+    let day = solution::find(2019, 3).unwrap();
+    assert_property(
+        "far wires parse",
+        &(integers(0, u64::MAX), integers(0, u64::MAX)),
+        |&(a, b): &(u64, u64)| {
+            let input = format!("R{},L1,R{}\nDL{},UR{}", a, b, b, a);
+            let near = parse_wires(&input)
+                .map_err(|error| error.to_string())?
+                .iter()
+                .all(|wire| stays_near(wire));
+            match day.parse(&input) {
+                Ok(_) if near => Ok(()),
+                Err(_) if !near => Ok(()),
+                Ok(_) => Err(format!("{:?} goes too far, but parsed", input)),
+                Err(error) => Err(format!("{:?} didn't parse: {}", input, error)),
+            }
+        },
+    );
+}
+
+#[test]
+fn ranges() {
+    assert_property(
+        "ranges parse",
+        &mutations(&["165432-707912", "0-0"], "0123456789- \n"),
+        |input: &String| round_trips(input, parse_range, |&(lo, hi)| format!("{}-{}", lo, hi)),
+    );
+    assert_property(
+        "ranges round trip",
+        &(integers(0, u64::MAX), integers(0, u64::MAX)),
+        |&(a, b): &(u64, u64)| {
+            let (lo, hi) = (a.min(b), a.max(b));
+            match parse_range(&format!("{}-{}", lo, hi)) {
+                Ok(range) if range == (lo, hi) => Ok(()),
+                parsed => Err(format!("parsed as {:?}", parsed)),
+            }
+        },
+    );
+}
+
+#[test]
+fn records() {
+    let parse = |input: &str| {
+        input::records::<u64, _>(input.as_bytes())
+            .map(|record| record.map(|record| record.value))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    assert_property(
+        "records parse",
+        &mutations(&["12\n14\n1969\n100756\n"], NOISE),
+        |input: &String| round_trips(input, parse, |masses| join(masses, "\n")),
+    );
+    assert_property(
+        "records round trip",
+        &vec_of(integers(0, u64::MAX), 20),
+        |masses: &Vec<u64>| match parse(&join(masses, "\n")) {
+            Ok(parsed) if &parsed == masses => Ok(()),
+            parsed => Err(format!("parsed as {:?}", parsed)),
+        },
+    );
+}
+
+#[test]
+fn big_integers() {
+    let parse = |input: &str| input.parse::<BigUint>();
+    assert_property(
+        "big integers parse",
+        &text("0123456789x", 60),
+        |input: &String| round_trips(input, parse, BigUint::to_string),
+    );
+    // Written without leading zeros, a number reads back as itself
+    assert_property(
+        "big integers round trip",
+        &text("0123456789", 60),
+        |digits: &String| {
+            let digits = match digits.trim_start_matches('0') {
+                "" => "0",
+                digits => digits,
+            };
+            match parse(digits).map(|n| n.to_string()) {
+                Ok(written) if written == digits => Ok(()),
+                written => Err(format!("{:?} was written as {:?}", digits, written)),
+            }
+        },
+    );
+}
+
+#[test]
+fn grids() {
+    assert_property("grids parse", &text("#.X\n", 40), |input: &String| {
+        round_trips(input, Grid::parse, Grid::to_string)
+    });
+    assert_property("grids parse", &text(NOISE, 40), |input: &String| {
+        round_trips(input, Grid::parse, Grid::to_string)
+    });
+}
+
+#[test]
+fn tool_formats() {
+    let baseline = "# year\tday\tstage\tmedian_ns\n2019\t4\tpart1\t10000000\n";
+    assert_property(
+        "baselines parse",
+        &mutations(&[baseline], "0123456789\t\n#part-"),
+        |input: &String| round_trips(input, Baseline::from_str, Baseline::to_string),
+    );
+    assert_property(
+        "stages parse",
+        &mutations(&["parse", "part1", "part2"], "parse12"),
+        |input: &String| round_trips(input, Stage::from_str, Stage::to_string),
+    );
+    assert_property(
+        "formats parse",
+        &mutations(&["text", "json", "tsv"], "textjsonv"),
+        |input: &String| Format::from_str(input).map(|_| ()).or(Ok(())),
+    );
+    assert_property(
+        "fixtures parse",
+        &mutations(&["# An example\npart1: 159\npart2: 610\n---\nR8\n"], NOISE),
+        |input: &String| {
+            drop(Fixture::parse("fixtures/2019/day03/example.txt", input));
+            Ok(())
+        },
+    );
+}
+
+/// Whether every point on `path` is within an `isize` of the port, worked out
+/// with `i128`s so it can't overflow itself.
+fn stays_near(path: &[Pathlet]) -> bool {
+    let fits = |n: i128| isize::MIN as i128 <= n && n <= isize::MAX as i128;
+    let (mut x, mut y) = (0i128, 0i128);
+    path.iter().all(|pathlet| {
+        let step = pathlet.step();
+        let distance = pathlet.distance() as i128;
+        x += step.x() as i128 * distance;
+        y += step.y() as i128 * distance;
+        fits(distance) && fits(x) && fits(y)
+    })
+}
+
+/// `values` written out, separated by `separator`.
+fn join<T: ToString>(values: &[T], separator: &str) -> String {
+    let values: Vec<String> = values.iter().map(T::to_string).collect();
+    values.join(separator)
+}